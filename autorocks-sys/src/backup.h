@@ -0,0 +1,114 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/utilities/backup_engine.h"
+#include "db.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Autocxx cannot access fields of non-pod type, so copy them (borrowed) into
+// a pod.
+struct BackupInfoPod
+{
+    uint32_t backup_id;
+    int64_t timestamp;
+    uint64_t size;
+    uint32_t number_files;
+    Slice app_metadata;
+};
+
+struct BackupInfosWrapper
+{
+    vector<BackupInfo> inner;
+
+    size_t len() const
+    {
+        return inner.size();
+    }
+
+    BackupInfoPod get(size_t i) const
+    {
+        const BackupInfo &info = inner[i];
+        return BackupInfoPod{
+            info.backup_id,
+            info.timestamp,
+            info.size,
+            info.number_files,
+            info.app_metadata,
+        };
+    }
+};
+
+struct BackupEngineWrapper
+{
+    unique_ptr<BackupEngine> engine;
+
+    Status open(Slice backup_dir)
+    {
+        BackupEngine *ptr;
+        BackupEngineOptions options(backup_dir.ToString());
+        Status status = BackupEngine::Open(options, Env::Default(), &ptr);
+        if (status.ok())
+        {
+            engine.reset(ptr);
+        }
+        return status;
+    }
+
+    // progress_fn is a `void (*)(void *)` called with ctx, or null.
+    Status create_new_backup(
+        const TransactionDBWrapper &db,
+        bool flush_before_backup,
+        void *ctx,
+        void *progress_fn,
+        uint32_t *backup_id)
+    {
+        CreateBackupOptions options;
+        options.flush_before_backup = flush_before_backup;
+        if (progress_fn != nullptr)
+        {
+            auto fn = reinterpret_cast<void (*)(void *)>(progress_fn);
+            options.progress_callback = [ctx, fn]()
+            { fn(ctx); };
+        }
+        return engine->CreateNewBackup(options, db.db.get(), backup_id);
+    }
+
+    void get_backup_info(BackupInfosWrapper *infos) const
+    {
+        engine->GetBackupInfo(&infos->inner);
+    }
+
+    Status verify_backup(uint32_t backup_id, bool verify_with_checksum) const
+    {
+        return engine->VerifyBackup(backup_id, verify_with_checksum);
+    }
+
+    Status purge_old_backups(uint32_t num_backups_to_keep)
+    {
+        return engine->PurgeOldBackups(num_backups_to_keep);
+    }
+
+    Status delete_backup(uint32_t backup_id)
+    {
+        return engine->DeleteBackup(backup_id);
+    }
+
+    Status restore_db_from_backup(
+        uint32_t backup_id,
+        Slice db_dir,
+        Slice wal_dir,
+        bool keep_log_files) const
+    {
+        return engine->RestoreDBFromBackup(
+            RestoreOptions(keep_log_files),
+            backup_id,
+            db_dir.ToString(),
+            wal_dir.ToString());
+    }
+};
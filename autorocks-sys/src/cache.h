@@ -0,0 +1,52 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/cache.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Note: make sure it's Unpin.
+struct CacheWrapper
+{
+    shared_ptr<Cache> cache;
+
+    size_t usage() const
+    {
+        return cache->GetUsage();
+    }
+
+    size_t pinned_usage() const
+    {
+        return cache->GetPinnedUsage();
+    }
+
+    size_t capacity() const
+    {
+        return cache->GetCapacity();
+    }
+
+    void set_capacity(size_t capacity) const
+    {
+        cache->SetCapacity(capacity);
+    }
+
+    void set_strict_capacity_limit(bool val) const
+    {
+        cache->SetStrictCapacityLimit(val);
+    }
+};
+
+CacheWrapper new_lru_cache(size_t capacity, int32_t num_shard_bits, bool strict_capacity_limit, double high_pri_pool_ratio)
+{
+    return CacheWrapper{NewLRUCache(capacity, num_shard_bits, strict_capacity_limit, high_pri_pool_ratio)};
+}
+
+CacheWrapper new_hyper_clock_cache(size_t capacity, size_t estimated_entry_charge, int32_t num_shard_bits, bool strict_capacity_limit)
+{
+    return CacheWrapper{HyperClockCacheOptions(capacity, estimated_entry_charge, num_shard_bits, strict_capacity_limit).MakeSharedCache()};
+}
@@ -7,6 +7,18 @@
 #include <memory>
 #include "rocksdb/utilities/transaction_db.h"
 #include "rocksdb/utilities/options_util.h"
+#include "rocksdb/utilities/db_ttl.h"
+#include "rocksdb/utilities/checkpoint.h"
+#include "options.h"
+#include "write_buffer_manager.h"
+#include "rate_limiter.h"
+#include "sst_file_manager.h"
+#include "env.h"
+#include "statistics.h"
+#include "event_listener.h"
+#include "write_batch.h"
+#include "metadata.h"
+#include "sst_file.h"
 
 using namespace std;
 using namespace rocksdb;
@@ -34,6 +46,33 @@ unique_ptr<WriteBatch> new_write_batch()
     return make_unique<WriteBatch>();
 }
 
+struct PropertyMapWrapper
+{
+    map<string, string> inner;
+    vector<pair<Slice, Slice>> entries;
+
+    // Call after inner is filled.
+    void index()
+    {
+        entries.assign(inner.begin(), inner.end());
+    }
+
+    size_t len() const
+    {
+        return entries.size();
+    }
+
+    Slice key(size_t index) const
+    {
+        return entries[index].first;
+    }
+
+    Slice value(size_t index) const
+    {
+        return entries[index].second;
+    }
+};
+
 // Autocxx cannot access fields of non-pod type...
 struct ReadOptionsWrapper : ReadOptions
 {
@@ -41,11 +80,78 @@ struct ReadOptionsWrapper : ReadOptions
     {
         snapshot = snapshot_;
     }
+
+    void set_prefix_same_as_start(bool val)
+    {
+        prefix_same_as_start = val;
+    }
+
+    void set_total_order_seek(bool val)
+    {
+        total_order_seek = val;
+    }
+
+    void set_auto_prefix_mode(bool val)
+    {
+        auto_prefix_mode = val;
+    }
+
+    // Microseconds since the epoch.
+    void set_deadline(uint64_t micros)
+    {
+        deadline = chrono::microseconds(micros);
+    }
+
+    void set_io_timeout(uint64_t micros)
+    {
+        io_timeout = chrono::microseconds(micros);
+    }
+
+    void set_timestamp(uint64_t ts)
+    {
+        timestamp_buf = encode_u64_ts(ts);
+        timestamp_slice = timestamp_buf;
+        timestamp = &timestamp_slice;
+    }
+
+    void set_iter_start_ts(uint64_t ts)
+    {
+        iter_start_ts_buf = encode_u64_ts(ts);
+        iter_start_ts_slice = iter_start_ts_buf;
+        iter_start_ts = &iter_start_ts_slice;
+    }
+
+    // Copy options, including the timestamps they point to.
+    void copy_from(const ReadOptions &options)
+    {
+        ReadOptions::operator=(options);
+        if (options.timestamp)
+        {
+            timestamp_buf = options.timestamp->ToString();
+            timestamp_slice = timestamp_buf;
+            timestamp = &timestamp_slice;
+        }
+        if (options.iter_start_ts)
+        {
+            iter_start_ts_buf = options.iter_start_ts->ToString();
+            iter_start_ts_slice = iter_start_ts_buf;
+            iter_start_ts = &iter_start_ts_slice;
+        }
+    }
+
+private:
+    // timestamp and iter_start_ts point to these.
+    string timestamp_buf;
+    Slice timestamp_slice;
+    string iter_start_ts_buf;
+    Slice iter_start_ts_slice;
 };
 
 struct DbOptionsWrapper
 {
     string path;
+    // Keeps db_options.env alive.
+    shared_ptr<Env> env;
     DBOptions db_options;
     vector<ColumnFamilyDescriptor> cf_descriptors;
 
@@ -74,6 +180,57 @@ struct DbOptionsWrapper
         db_options.create_missing_column_families = val;
     }
 
+    void set_db_write_buffer_size(size_t val)
+    {
+        db_options.db_write_buffer_size = val;
+    }
+
+    void set_write_buffer_manager(const WriteBufferManagerWrapper &manager)
+    {
+        db_options.write_buffer_manager = manager.manager;
+    }
+
+    void set_env(const SharedEnvWrapper &val)
+    {
+        env = val.env;
+        db_options.env = env.get();
+    }
+
+    void set_rate_limiter(const RateLimiterWrapper &limiter)
+    {
+        db_options.rate_limiter = limiter.limiter;
+    }
+
+    void set_sst_file_manager(const SstFileManagerWrapper &manager)
+    {
+        db_options.sst_file_manager = manager.manager;
+    }
+
+    void set_statistics(const StatisticsWrapper &statistics)
+    {
+        db_options.statistics = statistics.inner;
+    }
+
+    void add_event_listener(const EventListenerWrapper &listener)
+    {
+        db_options.listeners.push_back(listener.inner);
+    }
+
+    void set_wal_ttl_seconds(uint64_t val)
+    {
+        db_options.WAL_ttl_seconds = val;
+    }
+
+    void set_wal_size_limit_mb(uint64_t val)
+    {
+        db_options.WAL_size_limit_MB = val;
+    }
+
+    void set_allow_ingest_behind(bool val)
+    {
+        db_options.allow_ingest_behind = val;
+    }
+
     void set_compression(CompressionType comp)
     {
         for (ColumnFamilyDescriptor &x : cf_descriptors)
@@ -99,11 +256,69 @@ struct DbOptionsWrapper
         return &cf_descriptors[index].options;
     }
 
+    void set_cf_options(size_t index, const CfOptionsWrapper &options)
+    {
+        cf_descriptors[index].options = options.options;
+    }
+
+    size_t cf_len() const
+    {
+        return cf_descriptors.size();
+    }
+
     Status repair() const
     {
         return RepairDB(path, db_options, cf_descriptors);
     }
 
+    // Open the db without column family `col`, create it by importing
+    // `metadata`, then close the db.
+    //
+    // TransactionDB does not register imported column families with its lock
+    // manager, so this is done before the TransactionDB is opened.
+    Status import_cf(size_t col, const ExportImportFilesMetaDataWrapper &metadata, bool move_files) const
+    {
+        vector<ColumnFamilyDescriptor> descriptors;
+        for (size_t i = 0; i < cf_descriptors.size(); i++)
+        {
+            if (i != col)
+            {
+                descriptors.push_back(cf_descriptors[i]);
+            }
+        }
+        vector<ColumnFamilyHandle *> handles;
+        DB *ptr;
+        Status status = DB::Open(db_options, path, descriptors, &handles, &ptr);
+        if (!status.ok())
+        {
+            return status;
+        }
+        unique_ptr<DB> db(ptr);
+
+        ImportColumnFamilyOptions import_options;
+        import_options.move_files = move_files;
+        ColumnFamilyHandle *cf = nullptr;
+        status = db->CreateColumnFamilyWithImport(
+            cf_descriptors[col].options,
+            cf_descriptors[col].name,
+            import_options,
+            metadata.inner,
+            &cf);
+        if (cf)
+        {
+            handles.push_back(cf);
+        }
+        for (auto handle : handles)
+        {
+            db->DestroyColumnFamilyHandle(handle);
+        }
+        if (!status.ok())
+        {
+            return status;
+        }
+        return db->Close();
+    }
+
 private:
     void sort_and_complete_missing(size_t columns)
     {
@@ -139,16 +354,22 @@ struct TransactionWrapper;
 // Note: make sure TransactionDBWrapper is Unpin.
 struct TransactionDBWrapper
 {
+    // Must outlive db.
+    shared_ptr<Env> env;
     unique_ptr<TransactionDB> db;
     std::vector<ColumnFamilyHandle *> cf_handles;
+    shared_ptr<BackgroundErrorTracker> bg_error_tracker;
 
     Status open(
         const DbOptionsWrapper &options,
         const TransactionDBOptions &transaction_db_options)
     {
         TransactionDB *ptr;
+        DBOptions db_options = options.db_options;
+        bg_error_tracker = make_shared<BackgroundErrorTracker>();
+        db_options.listeners.push_back(bg_error_tracker);
         Status status = TransactionDB::Open(
-            options.db_options,
+            db_options,
             transaction_db_options,
             options.path,
             options.cf_descriptors,
@@ -157,6 +378,7 @@ struct TransactionDBWrapper
         if (status.ok())
         {
             db.reset(ptr);
+            env = options.env;
         }
         return status;
     }
@@ -271,11 +493,71 @@ struct TransactionDBWrapper
         return db->Delete(options, cf, key);
     }
 
+    Status get_entity(const ReadOptions &options, ColumnFamilyHandle *cf, const Slice &key, PinnableWideColumnsWrapper *columns) const
+    {
+        return db->GetEntity(options, cf, key, &columns->inner);
+    }
+
     bool get_int_property(ColumnFamilyHandle *cf, const Slice &property, uint64_t *value) const
     {
         return db->GetIntProperty(cf, property, value);
     }
 
+    uint32_t get_cf_id(size_t cf) const
+    {
+        return cf_handles[cf]->GetID();
+    }
+
+    uint64_t latest_sequence_number() const
+    {
+        return db->GetLatestSequenceNumber();
+    }
+
+    Status get_updates_since(uint64_t sequence, TransactionLogIteratorWrapper *iter) const
+    {
+        return db->GetUpdatesSince(sequence, &iter->inner, TransactionLogIterator::ReadOptions());
+    }
+
+    Status background_error() const
+    {
+        return bg_error_tracker->get();
+    }
+
+    Status resume() const
+    {
+        Status status = db->Resume();
+        if (status.ok())
+        {
+            bg_error_tracker->clear();
+        }
+        return status;
+    }
+
+    unique_ptr<string> get_property(ColumnFamilyHandle *cf, const Slice &property) const
+    {
+        auto value = make_unique<string>();
+        if (!db->GetProperty(cf, property, value.get()))
+        {
+            return nullptr;
+        }
+        return value;
+    }
+
+    bool get_map_property(ColumnFamilyHandle *cf, const Slice &property, PropertyMapWrapper *value) const
+    {
+        if (!db->GetMapProperty(cf, property, &value->inner))
+        {
+            return false;
+        }
+        value->index();
+        return true;
+    }
+
+    bool get_aggregated_int_property(const Slice &property, uint64_t *value) const
+    {
+        return db->GetAggregatedIntProperty(property, value);
+    }
+
     unique_ptr<Iterator> iter(const ReadOptions &options, ColumnFamilyHandle *cf) const
     {
         return unique_ptr<Iterator>(db->NewIterator(options, cf));
@@ -285,7 +567,66 @@ struct TransactionDBWrapper
 
     Status write(const WriteOptions &wopts, const TransactionDBWriteOptimizations &opts, WriteBatch *updates) const
     {
-        return db->Write(wopts, opts, updates);
+        if (opts.skip_concurrency_control || !updates->HasPutEntity())
+        {
+            return db->Write(wopts, opts, updates);
+        }
+        // TransactionDB can't lock the keys of entities in this RocksDB
+        // version, so lock all keys of the batch with a transaction and write
+        // it without concurrency control while holding the locks.
+        BatchKeyCollector collector;
+        Status status = updates->Iterate(&collector);
+        if (!status.ok())
+        {
+            return status;
+        }
+        unique_ptr<Transaction> tx(db->BeginTransaction(wopts));
+        for (const auto &[cf_id, key] : collector.keys)
+        {
+            ColumnFamilyHandle *cf = get_cf_by_id(cf_id);
+            if (cf == nullptr)
+            {
+                return Status::InvalidArgument("unknown column family");
+            }
+            status = tx->GetForUpdate(ReadOptions(), cf, key, static_cast<string *>(nullptr), true, false);
+            if (!status.ok())
+            {
+                return status;
+            }
+        }
+        TransactionDBWriteOptimizations locked = opts;
+        locked.skip_concurrency_control = true;
+        status = db->Write(wopts, locked, updates);
+        // Releases the locks.
+        tx->Rollback();
+        return status;
+    }
+
+    ColumnFamilyHandle *get_cf_by_id(uint32_t id) const
+    {
+        for (ColumnFamilyHandle *cf : cf_handles)
+        {
+            // Dropped column families are null.
+            if (cf != nullptr && cf->GetID() == id)
+            {
+                return cf;
+            }
+        }
+        return nullptr;
+    }
+
+    Status increase_full_history_ts_low(ColumnFamilyHandle *cf, uint64_t ts_low) const
+    {
+        return db->IncreaseFullHistoryTsLow(cf, encode_u64_ts(ts_low));
+    }
+
+    // ts_low is 0 if it was never increased.
+    Status get_full_history_ts_low(ColumnFamilyHandle *cf, uint64_t *ts_low) const
+    {
+        string buf;
+        Status status = db->GetFullHistoryTsLow(cf, &buf);
+        *ts_low = decode_u64_ts(buf);
+        return status;
     }
 
     const Snapshot *get_snapshot() const
@@ -297,11 +638,84 @@ struct TransactionDBWrapper
     {
         db->ReleaseSnapshot(snapshot);
     }
+
+    void column_family_metadata(ColumnFamilyHandle *cf, ColumnFamilyMetaDataWrapper *metadata) const
+    {
+        db->GetColumnFamilyMetaData(cf, &metadata->inner);
+    }
+
+    void live_files_metadata(LiveFilesMetaDataWrapper *metadata) const
+    {
+        db->GetLiveFilesMetaData(&metadata->inner);
+    }
+
+    Status properties_of_all_tables(ColumnFamilyHandle *cf, TablePropertiesCollectionWrapper *props) const
+    {
+        TablePropertiesCollection collection;
+        Status status = db->GetPropertiesOfAllTables(cf, &collection);
+        props->inner.assign(collection.begin(), collection.end());
+        return status;
+    }
+
+    Status ingest_external_files(
+        ColumnFamilyHandle *cf,
+        Slice const *paths,
+        size_t len,
+        const IngestOptionsWrapper &options) const
+    {
+        vector<string> files;
+        files.reserve(len);
+        for (size_t i = 0; i < len; i++)
+        {
+            files.push_back(paths[i].ToString());
+        }
+        return db->IngestExternalFile(cf, files, options.options);
+    }
+
+    // Null begin or end means unbounded.
+    Status compact_range(ColumnFamilyHandle *cf, Slice const *begin, Slice const *end) const
+    {
+        return db->CompactRange(CompactRangeOptions(), cf, begin, end);
+    }
+
+    Status create_checkpoint(Slice dir, uint64_t log_size_for_flush, uint64_t *sequence_number) const
+    {
+        Checkpoint *ptr;
+        Status status = Checkpoint::Create(db.get(), &ptr);
+        if (!status.ok())
+        {
+            return status;
+        }
+        unique_ptr<Checkpoint> checkpoint(ptr);
+        return checkpoint->CreateCheckpoint(dir.ToString(), log_size_for_flush, sequence_number);
+    }
+
+    Status export_cf(ColumnFamilyHandle *cf, Slice dir, ExportImportFilesMetaDataWrapper *metadata) const
+    {
+        Checkpoint *ptr;
+        Status status = Checkpoint::Create(db.get(), &ptr);
+        if (!status.ok())
+        {
+            return status;
+        }
+        unique_ptr<Checkpoint> checkpoint(ptr);
+        ExportImportFilesMetaData *exported;
+        status = checkpoint->ExportColumnFamily(cf, dir.ToString(), &exported);
+        if (!status.ok())
+        {
+            return status;
+        }
+        metadata->inner = move(*exported);
+        delete exported;
+        return status;
+    }
 };
 
 // Note: make sure ReadOnlyDbWrapper is Unpin.
 struct ReadOnlyDbWrapper
 {
+    // Must outlive db.
+    shared_ptr<Env> env;
     unique_ptr<DB> db;
     std::vector<ColumnFamilyHandle *> cf_handles;
 
@@ -318,6 +732,7 @@ struct ReadOnlyDbWrapper
         if (status.ok())
         {
             db.reset(ptr);
+            env = options.env;
         }
         return status;
     }
@@ -349,6 +764,124 @@ struct ReadOnlyDbWrapper
         return db->Get(options, cf, key, slice);
     }
 
+    Status get_entity(const ReadOptions &options, ColumnFamilyHandle *cf, const Slice &key, PinnableWideColumnsWrapper *columns) const
+    {
+        return db->GetEntity(options, cf, key, &columns->inner);
+    }
+
+    bool get_int_property(ColumnFamilyHandle *cf, const Slice &property, uint64_t *value) const
+    {
+        return db->GetIntProperty(cf, property, value);
+    }
+
+    unique_ptr<string> get_property(ColumnFamilyHandle *cf, const Slice &property) const
+    {
+        auto value = make_unique<string>();
+        if (!db->GetProperty(cf, property, value.get()))
+        {
+            return nullptr;
+        }
+        return value;
+    }
+
+    bool get_map_property(ColumnFamilyHandle *cf, const Slice &property, PropertyMapWrapper *value) const
+    {
+        if (!db->GetMapProperty(cf, property, &value->inner))
+        {
+            return false;
+        }
+        value->index();
+        return true;
+    }
+
+    bool get_aggregated_int_property(const Slice &property, uint64_t *value) const
+    {
+        return db->GetAggregatedIntProperty(property, value);
+    }
+
+    unique_ptr<Iterator> iter(const ReadOptions &options, ColumnFamilyHandle *cf) const
+    {
+        return unique_ptr<Iterator>(db->NewIterator(options, cf));
+    }
+};
+
+// Note: make sure TtlDbWrapper is Unpin.
+struct TtlDbWrapper
+{
+    // Must outlive db.
+    shared_ptr<Env> env;
+    unique_ptr<DBWithTTL> db;
+    std::vector<ColumnFamilyHandle *> cf_handles;
+
+    // ttls should have an entry (in seconds) for each column family.
+    Status open(
+        const DbOptionsWrapper &options,
+        int32_t const *ttls,
+        size_t len)
+    {
+        DBWithTTL *ptr;
+        Status status = DBWithTTL::Open(
+            options.db_options,
+            options.path,
+            options.cf_descriptors,
+            &cf_handles,
+            &ptr,
+            vector<int32_t>(ttls, ttls + len));
+        if (status.ok())
+        {
+            db.reset(ptr);
+            env = options.env;
+        }
+        return status;
+    }
+
+    ~TtlDbWrapper()
+    {
+        for (auto cf : cf_handles)
+        {
+            db->DestroyColumnFamilyHandle(cf);
+        }
+    }
+
+    ColumnFamilyHandle *get_cf(size_t cf) const
+    {
+        if (cf >= cf_handles.size())
+        {
+            return nullptr;
+        }
+        return cf_handles[cf];
+    }
+
+    size_t default_col() const
+    {
+        return cf_handles.size() - 1;
+    }
+
+    void set_ttl(ColumnFamilyHandle *cf, int32_t ttl) const
+    {
+        db->SetTtl(cf, ttl);
+    }
+
+    Status get(const ReadOptions &options, ColumnFamilyHandle *cf, const Slice &key, PinnableSlice *slice) const
+    {
+        return db->Get(options, cf, key, slice);
+    }
+
+    Status put(const WriteOptions &options, ColumnFamilyHandle *cf, const Slice &key, const Slice &value) const
+    {
+        return db->Put(options, cf, key, value);
+    }
+
+    Status del(const WriteOptions &options, ColumnFamilyHandle *cf, const Slice &key) const
+    {
+        return db->Delete(options, cf, key);
+    }
+
+    bool get_int_property(ColumnFamilyHandle *cf, const Slice &property, uint64_t *value) const
+    {
+        return db->GetIntProperty(cf, property, value);
+    }
+
     unique_ptr<Iterator> iter(const ReadOptions &options, ColumnFamilyHandle *cf) const
     {
         return unique_ptr<Iterator>(db->NewIterator(options, cf));
@@ -397,6 +930,16 @@ struct TransactionWrapper
         return SharedSnapshotWrapper{tx->GetTimestampedSnapshot()};
     }
 
+    Status set_commit_timestamp(uint64_t ts)
+    {
+        return tx->SetCommitTimestamp(ts);
+    }
+
+    Status set_read_timestamp_for_validation(uint64_t ts)
+    {
+        return tx->SetReadTimestampForValidation(ts);
+    }
+
     Status rollback()
     {
         return tx->Rollback();
@@ -0,0 +1,345 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <atomic>
+#include <memory>
+#include <mutex>
+#include <unordered_map>
+#include "rocksdb/env.h"
+#include "rocksdb/file_system.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Note: make sure it's Unpin.
+struct SharedEnvWrapper
+{
+    shared_ptr<Env> env;
+};
+
+SharedEnvWrapper default_env()
+{
+    // Env::Default() is static, don't delete it.
+    return SharedEnvWrapper{shared_ptr<Env>(Env::Default(), [](Env *) {})};
+}
+
+SharedEnvWrapper new_mem_env()
+{
+    return SharedEnvWrapper{shared_ptr<Env>(NewMemEnv(Env::Default()))};
+}
+
+// Simulates losing unsynced data on a crash, and write errors, on top of
+// another file system. Only files written through this file system are
+// affected.
+class FaultInjectionFs : public FileSystemWrapper
+{
+public:
+    explicit FaultInjectionFs(const shared_ptr<FileSystem> &base)
+        : FileSystemWrapper(base), fail_writes(false)
+    {
+    }
+
+    static const char *kClassName()
+    {
+        return "AutorocksFaultInjectionFs";
+    }
+
+    const char *Name() const override
+    {
+        return kClassName();
+    }
+
+    IOStatus NewWritableFile(const string &fname, const FileOptions &options, unique_ptr<FSWritableFile> *result, IODebugContext *dbg) override
+    {
+        if (fail_writes)
+        {
+            return injected_error();
+        }
+        IOStatus s = target()->NewWritableFile(fname, options, result, dbg);
+        if (s.ok())
+        {
+            track(fname, 0);
+            result->reset(new File(move(*result), this, fname, 0));
+        }
+        return s;
+    }
+
+    IOStatus ReopenWritableFile(const string &fname, const FileOptions &options, unique_ptr<FSWritableFile> *result, IODebugContext *dbg) override
+    {
+        if (fail_writes)
+        {
+            return injected_error();
+        }
+        uint64_t size = 0;
+        IOStatus s = target()->GetFileSize(fname, IOOptions(), &size, dbg);
+        if (s.IsNotFound())
+        {
+            size = 0;
+        }
+        else if (!s.ok())
+        {
+            return s;
+        }
+        s = target()->ReopenWritableFile(fname, options, result, dbg);
+        if (s.ok())
+        {
+            // Existing data is assumed to be synced, unless it was written
+            // through this file system.
+            lock_guard<mutex> lock(mu);
+            synced_sizes.emplace(fname, size);
+            result->reset(new File(move(*result), this, fname, size));
+        }
+        return s;
+    }
+
+    IOStatus ReuseWritableFile(const string &fname, const string &old_fname, const FileOptions &options, unique_ptr<FSWritableFile> *result, IODebugContext *dbg) override
+    {
+        if (fail_writes)
+        {
+            return injected_error();
+        }
+        IOStatus s = target()->ReuseWritableFile(fname, old_fname, options, result, dbg);
+        if (s.ok())
+        {
+            lock_guard<mutex> lock(mu);
+            synced_sizes.erase(old_fname);
+            synced_sizes[fname] = 0;
+            result->reset(new File(move(*result), this, fname, 0));
+        }
+        return s;
+    }
+
+    IOStatus RenameFile(const string &src, const string &target_name, const IOOptions &options, IODebugContext *dbg) override
+    {
+        IOStatus s = target()->RenameFile(src, target_name, options, dbg);
+        if (s.ok())
+        {
+            lock_guard<mutex> lock(mu);
+            auto it = synced_sizes.find(src);
+            if (it != synced_sizes.end())
+            {
+                synced_sizes[target_name] = it->second;
+                synced_sizes.erase(src);
+            }
+        }
+        return s;
+    }
+
+    IOStatus DeleteFile(const string &fname, const IOOptions &options, IODebugContext *dbg) override
+    {
+        IOStatus s = target()->DeleteFile(fname, options, dbg);
+        if (s.ok())
+        {
+            lock_guard<mutex> lock(mu);
+            synced_sizes.erase(fname);
+        }
+        return s;
+    }
+
+    void set_fail_writes(bool val)
+    {
+        fail_writes = val;
+    }
+
+    // Truncate files to their size at the last sync. The files must not be
+    // open for writing, i.e. the db must be closed.
+    IOStatus drop_unsynced_data()
+    {
+        lock_guard<mutex> lock(mu);
+        for (auto &entry : synced_sizes)
+        {
+            uint64_t size = 0;
+            IOStatus s = target()->GetFileSize(entry.first, IOOptions(), &size, nullptr);
+            if (s.IsNotFound())
+            {
+                continue;
+            }
+            if (!s.ok())
+            {
+                return s;
+            }
+            if (size <= entry.second)
+            {
+                continue;
+            }
+            unique_ptr<FSWritableFile> file;
+            s = target()->ReopenWritableFile(entry.first, FileOptions(), &file, nullptr);
+            if (s.ok())
+            {
+                s = file->Truncate(entry.second, IOOptions(), nullptr);
+            }
+            if (s.ok())
+            {
+                s = file->Close(IOOptions(), nullptr);
+            }
+            if (!s.ok())
+            {
+                return s;
+            }
+        }
+        return IOStatus::OK();
+    }
+
+private:
+    class File : public FSWritableFileOwnerWrapper
+    {
+    public:
+        File(unique_ptr<FSWritableFile> &&target, FaultInjectionFs *fs_, string fname_, uint64_t size)
+            : FSWritableFileOwnerWrapper(move(target)), fs(fs_), fname(move(fname_)), pos(size)
+        {
+        }
+
+        IOStatus Append(const Slice &data, const IOOptions &options, IODebugContext *dbg) override
+        {
+            if (fs->fail_writes)
+            {
+                return injected_error();
+            }
+            IOStatus s = target()->Append(data, options, dbg);
+            if (s.ok())
+            {
+                pos += data.size();
+            }
+            return s;
+        }
+
+        IOStatus Append(const Slice &data, const IOOptions &options, const DataVerificationInfo &info, IODebugContext *dbg) override
+        {
+            if (fs->fail_writes)
+            {
+                return injected_error();
+            }
+            IOStatus s = target()->Append(data, options, info, dbg);
+            if (s.ok())
+            {
+                pos += data.size();
+            }
+            return s;
+        }
+
+        IOStatus PositionedAppend(const Slice &data, uint64_t offset, const IOOptions &options, IODebugContext *dbg) override
+        {
+            if (fs->fail_writes)
+            {
+                return injected_error();
+            }
+            IOStatus s = target()->PositionedAppend(data, offset, options, dbg);
+            if (s.ok())
+            {
+                pos = max(pos, offset + data.size());
+            }
+            return s;
+        }
+
+        IOStatus PositionedAppend(const Slice &data, uint64_t offset, const IOOptions &options, const DataVerificationInfo &info, IODebugContext *dbg) override
+        {
+            if (fs->fail_writes)
+            {
+                return injected_error();
+            }
+            IOStatus s = target()->PositionedAppend(data, offset, options, info, dbg);
+            if (s.ok())
+            {
+                pos = max(pos, offset + data.size());
+            }
+            return s;
+        }
+
+        IOStatus Truncate(uint64_t size, const IOOptions &options, IODebugContext *dbg) override
+        {
+            IOStatus s = target()->Truncate(size, options, dbg);
+            if (s.ok())
+            {
+                pos = size;
+            }
+            return s;
+        }
+
+        IOStatus Flush(const IOOptions &options, IODebugContext *dbg) override
+        {
+            if (fs->fail_writes)
+            {
+                return injected_error();
+            }
+            return target()->Flush(options, dbg);
+        }
+
+        IOStatus Sync(const IOOptions &options, IODebugContext *dbg) override
+        {
+            if (fs->fail_writes)
+            {
+                return injected_error();
+            }
+            IOStatus s = target()->Sync(options, dbg);
+            if (s.ok())
+            {
+                fs->track(fname, pos);
+            }
+            return s;
+        }
+
+        IOStatus Fsync(const IOOptions &options, IODebugContext *dbg) override
+        {
+            if (fs->fail_writes)
+            {
+                return injected_error();
+            }
+            IOStatus s = target()->Fsync(options, dbg);
+            if (s.ok())
+            {
+                fs->track(fname, pos);
+            }
+            return s;
+        }
+
+    private:
+        FaultInjectionFs *fs;
+        string fname;
+        uint64_t pos;
+    };
+
+    static IOStatus injected_error()
+    {
+        return IOStatus::IOError("Injected write error");
+    }
+
+    void track(const string &fname, uint64_t synced_size)
+    {
+        lock_guard<mutex> lock(mu);
+        synced_sizes[fname] = synced_size;
+    }
+
+    atomic<bool> fail_writes;
+    mutex mu;
+    // Size of each written file at its last sync.
+    unordered_map<string, uint64_t> synced_sizes;
+};
+
+// Note: make sure it's Unpin.
+struct FaultInjectionFsWrapper
+{
+    shared_ptr<FaultInjectionFs> fs;
+
+    void set_fail_writes(bool val) const
+    {
+        fs->set_fail_writes(val);
+    }
+
+    Status drop_unsynced_data() const
+    {
+        return fs->drop_unsynced_data();
+    }
+
+    SharedEnvWrapper env() const
+    {
+        return SharedEnvWrapper{NewCompositeEnv(fs)};
+    }
+};
+
+FaultInjectionFsWrapper new_fault_injection_fs(const SharedEnvWrapper &base)
+{
+    return FaultInjectionFsWrapper{make_shared<FaultInjectionFs>(base.env->GetFileSystem())};
+}
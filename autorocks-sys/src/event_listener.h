@@ -0,0 +1,247 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include <mutex>
+#include "rocksdb/listener.h"
+#include "metadata.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Autocxx cannot access fields of non-pod type, so copy them (borrowed) into
+// pods.
+struct FlushJobInfoPod
+{
+    Slice cf_name;
+    Slice file_path;
+    uint64_t thread_id;
+    int32_t job_id;
+    bool triggered_writes_slowdown;
+    bool triggered_writes_stop;
+    uint64_t smallest_seqno;
+    uint64_t largest_seqno;
+    FlushReason flush_reason;
+    TablePropertiesPod table_properties;
+};
+
+struct CompactionJobInfoPod
+{
+    Slice cf_name;
+    uint64_t thread_id;
+    int32_t job_id;
+    int32_t base_input_level;
+    int32_t output_level;
+    const Slice *input_files;
+    size_t input_files_len;
+    const Slice *output_files;
+    size_t output_files_len;
+    CompactionReason compaction_reason;
+    uint64_t elapsed_micros;
+    uint64_t num_input_records;
+    uint64_t num_output_records;
+    uint64_t total_input_bytes;
+    uint64_t total_output_bytes;
+};
+
+struct WriteStallInfoPod
+{
+    Slice cf_name;
+    WriteStallCondition cur;
+    WriteStallCondition prev;
+};
+
+struct TableFileCreationInfoPod
+{
+    Slice db_name;
+    Slice cf_name;
+    Slice file_path;
+    int32_t job_id;
+    TableFileCreationReason reason;
+    uint64_t file_size;
+    TablePropertiesPod table_properties;
+};
+
+// Callbacks implemented in rust. Each fn takes ctx as the first argument,
+// then a pointer to the info pod and, where there is one, a pointer to the
+// status.
+struct RustEventListener : EventListener
+{
+    typedef void (*OnFlushCompletedFn)(void *, const FlushJobInfoPod *);
+    typedef void (*OnCompactionCompletedFn)(void *, const CompactionJobInfoPod *, const Status *);
+    typedef void (*OnStallConditionsChangedFn)(void *, const WriteStallInfoPod *);
+    typedef void (*OnBackgroundErrorFn)(void *, const BackgroundErrorReason *, const Status *);
+    typedef void (*OnTableFileCreatedFn)(void *, const TableFileCreationInfoPod *, const Status *);
+    typedef void (*DropFn)(void *);
+
+    void *ctx;
+    OnFlushCompletedFn on_flush_completed_fn;
+    OnCompactionCompletedFn on_compaction_completed_fn;
+    OnStallConditionsChangedFn on_stall_conditions_changed_fn;
+    OnBackgroundErrorFn on_background_error_fn;
+    OnTableFileCreatedFn on_table_file_created_fn;
+    DropFn drop_fn;
+
+    RustEventListener(
+        void *ctx_,
+        void *on_flush_completed_fn_,
+        void *on_compaction_completed_fn_,
+        void *on_stall_conditions_changed_fn_,
+        void *on_background_error_fn_,
+        void *on_table_file_created_fn_,
+        void *drop_fn_)
+        : ctx(ctx_),
+          on_flush_completed_fn(reinterpret_cast<OnFlushCompletedFn>(on_flush_completed_fn_)),
+          on_compaction_completed_fn(reinterpret_cast<OnCompactionCompletedFn>(on_compaction_completed_fn_)),
+          on_stall_conditions_changed_fn(reinterpret_cast<OnStallConditionsChangedFn>(on_stall_conditions_changed_fn_)),
+          on_background_error_fn(reinterpret_cast<OnBackgroundErrorFn>(on_background_error_fn_)),
+          on_table_file_created_fn(reinterpret_cast<OnTableFileCreatedFn>(on_table_file_created_fn_)),
+          drop_fn(reinterpret_cast<DropFn>(drop_fn_))
+    {
+    }
+
+    ~RustEventListener()
+    {
+        drop_fn(ctx);
+    }
+
+    const char *Name() const override
+    {
+        return "RustEventListener";
+    }
+
+    void OnFlushCompleted(DB *, const FlushJobInfo &info) override
+    {
+        FlushJobInfoPod pod{
+            info.cf_name,
+            info.file_path,
+            info.thread_id,
+            info.job_id,
+            info.triggered_writes_slowdown,
+            info.triggered_writes_stop,
+            info.smallest_seqno,
+            info.largest_seqno,
+            info.flush_reason,
+            table_properties_to_pod(info.table_properties),
+        };
+        on_flush_completed_fn(ctx, &pod);
+    }
+
+    void OnCompactionCompleted(DB *, const CompactionJobInfo &info) override
+    {
+        vector<Slice> input_files(info.input_files.begin(), info.input_files.end());
+        vector<Slice> output_files(info.output_files.begin(), info.output_files.end());
+        CompactionJobInfoPod pod{
+            info.cf_name,
+            info.thread_id,
+            info.job_id,
+            info.base_input_level,
+            info.output_level,
+            input_files.data(),
+            input_files.size(),
+            output_files.data(),
+            output_files.size(),
+            info.compaction_reason,
+            info.stats.elapsed_micros,
+            info.stats.num_input_records,
+            info.stats.num_output_records,
+            info.stats.total_input_bytes,
+            info.stats.total_output_bytes,
+        };
+        on_compaction_completed_fn(ctx, &pod, &info.status);
+    }
+
+    void OnStallConditionsChanged(const WriteStallInfo &info) override
+    {
+        WriteStallInfoPod pod{
+            info.cf_name,
+            info.condition.cur,
+            info.condition.prev,
+        };
+        on_stall_conditions_changed_fn(ctx, &pod);
+    }
+
+    void OnBackgroundError(BackgroundErrorReason reason, Status *bg_error) override
+    {
+        on_background_error_fn(ctx, &reason, bg_error);
+    }
+
+    void OnTableFileCreated(const TableFileCreationInfo &info) override
+    {
+        TableFileCreationInfoPod pod{
+            info.db_name,
+            info.cf_name,
+            info.file_path,
+            info.job_id,
+            info.reason,
+            info.file_size,
+            table_properties_to_pod(info.table_properties),
+        };
+        on_table_file_created_fn(ctx, &pod, &info.status);
+    }
+};
+
+// Remembers the current background error, since RocksDB has no API to get
+// it. Registered last, so that errors suppressed by other listeners are
+// ignored.
+struct BackgroundErrorTracker : EventListener
+{
+    mutable mutex mu;
+    Status error;
+
+    const char *Name() const override
+    {
+        return "BackgroundErrorTracker";
+    }
+
+    void OnBackgroundError(BackgroundErrorReason, Status *bg_error) override
+    {
+        lock_guard<mutex> lock(mu);
+        error = *bg_error;
+    }
+
+    void OnErrorRecoveryEnd(const BackgroundErrorRecoveryInfo &info) override
+    {
+        lock_guard<mutex> lock(mu);
+        error = info.new_bg_error;
+    }
+
+    Status get() const
+    {
+        lock_guard<mutex> lock(mu);
+        return error;
+    }
+
+    void clear()
+    {
+        lock_guard<mutex> lock(mu);
+        error = Status::OK();
+    }
+};
+
+struct EventListenerWrapper
+{
+    shared_ptr<EventListener> inner;
+};
+
+EventListenerWrapper new_rust_event_listener(
+    void *ctx,
+    void *on_flush_completed_fn,
+    void *on_compaction_completed_fn,
+    void *on_stall_conditions_changed_fn,
+    void *on_background_error_fn,
+    void *on_table_file_created_fn,
+    void *drop_fn)
+{
+    return EventListenerWrapper{make_shared<RustEventListener>(
+        ctx,
+        on_flush_completed_fn,
+        on_compaction_completed_fn,
+        on_stall_conditions_changed_fn,
+        on_background_error_fn,
+        on_table_file_created_fn,
+        drop_fn)};
+}
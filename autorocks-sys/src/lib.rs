@@ -6,6 +6,8 @@ autocxx::include_cpp! {
     #include "rocksdb/iterator.h"
     #include "rocksdb/status.h"
     #include "db.h"
+    #include "backup.h"
+    #include "perf_context.h"
 
     safety!(unsafe_ffi)
 
@@ -30,19 +32,96 @@ autocxx::include_cpp! {
     generate!("new_transaction_db_options")
     generate!("new_write_batch")
     generate!("ReadOptionsWrapper")
+    generate!("PropertyMapWrapper")
     generate!("DbOptionsWrapper")
+    generate!("CfOptionsWrapper")
+    generate!("BlockBasedOptionsWrapper")
+    generate!("CacheWrapper")
+    generate!("new_lru_cache")
+    generate!("new_hyper_clock_cache")
+    generate!("SliceTransformWrapper")
+    generate!("new_fixed_prefix_transform")
+    generate!("new_capped_prefix_transform")
+    generate!("new_rust_slice_transform")
+    generate!("WriteBufferManagerWrapper")
+    generate!("new_write_buffer_manager")
+    generate!("RateLimiterWrapper")
+    generate!("new_rate_limiter")
+    generate!("TrackedFilesWrapper")
+    generate!("SstFileManagerWrapper")
+    generate!("new_sst_file_manager")
+    generate!("SharedEnvWrapper")
+    generate!("default_env")
+    generate!("new_mem_env")
+    generate!("FaultInjectionFsWrapper")
+    generate!("new_fault_injection_fs")
+    generate!("rocksdb::Tickers")
+    generate!("rocksdb::Histograms")
+    generate!("rocksdb::StatsLevel")
+    generate_pod!("rocksdb::HistogramData")
+    generate!("StatisticsWrapper")
+    generate!("rocksdb::PerfLevel")
+    generate!("set_perf_level")
+    generate!("get_perf_level")
+    generate_pod!("PerfContextPod")
+    generate!("perf_context_snapshot")
+    generate!("reset_perf_context")
+    generate_pod!("IOStatsContextPod")
+    generate!("iostats_context_snapshot")
+    generate!("reset_iostats_context")
+    generate!("rocksdb::FlushReason")
+    generate!("rocksdb::CompactionReason")
+    generate!("rocksdb::WriteStallCondition")
+    generate!("rocksdb::BackgroundErrorReason")
+    generate!("rocksdb::TableFileCreationReason")
+    generate_pod!("FlushJobInfoPod")
+    generate_pod!("CompactionJobInfoPod")
+    generate_pod!("WriteStallInfoPod")
+    generate_pod!("TableFileCreationInfoPod")
+    generate!("EventListenerWrapper")
+    generate!("new_rust_event_listener")
+    generate!("iterate_write_batch")
+    generate!("write_batch_put_entity")
+    generate_pod!("WideColumnPod")
+    generate!("PinnableWideColumnsWrapper")
+    generate!("iterator_columns_len")
+    generate!("iterator_column")
+    generate!("TransactionLogIteratorWrapper")
+    generate!("new_statistics")
+    generate_pod!("LiveFileMetaDataPod")
+    generate_pod!("TablePropertiesPod")
+    generate!("ExportImportFilesMetaDataWrapper")
+    generate!("ColumnFamilyMetaDataWrapper")
+    generate!("LiveFilesMetaDataWrapper")
+    generate!("TablePropertiesCollectionWrapper")
+    generate_pod!("ExternalSstFileInfoPod")
+    generate!("SstFileWriterWrapper")
+    generate!("IngestOptionsWrapper")
+    generate!("SstFileReaderWrapper")
     generate!("TransactionDBWrapper")
     generate!("SharedSnapshotWrapper")
     generate!("ReadOnlyDbWrapper")
+    generate!("TtlDbWrapper")
     generate!("TransactionWrapper")
+    generate_pod!("BackupInfoPod")
+    generate!("BackupInfosWrapper")
+    generate!("BackupEngineWrapper")
 }
 
 pub use ffi::*;
 
 impl Unpin for TransactionDBWrapper {}
 impl Unpin for ReadOnlyDbWrapper {}
+impl Unpin for TtlDbWrapper {}
 impl Unpin for TransactionWrapper {}
 impl Unpin for SharedSnapshotWrapper {}
+impl Unpin for CacheWrapper {}
+impl Unpin for WriteBufferManagerWrapper {}
+impl Unpin for RateLimiterWrapper {}
+impl Unpin for SstFileManagerWrapper {}
+impl Unpin for SharedEnvWrapper {}
+impl Unpin for FaultInjectionFsWrapper {}
+impl Unpin for StatisticsWrapper {}
 
 unsafe impl Send for TransactionDBWrapper {}
 unsafe impl Sync for TransactionDBWrapper {}
@@ -50,6 +129,9 @@ unsafe impl Sync for TransactionDBWrapper {}
 unsafe impl Send for ReadOnlyDbWrapper {}
 unsafe impl Sync for ReadOnlyDbWrapper {}
 
+unsafe impl Send for TtlDbWrapper {}
+unsafe impl Sync for TtlDbWrapper {}
+
 unsafe impl Send for TransactionWrapper {}
 // Sync because mutable methods take Pin<&mut Self>.
 unsafe impl Sync for TransactionWrapper {}
@@ -57,6 +139,35 @@ unsafe impl Sync for TransactionWrapper {}
 unsafe impl Send for SharedSnapshotWrapper {}
 unsafe impl Sync for SharedSnapshotWrapper {}
 
+unsafe impl Send for CacheWrapper {}
+unsafe impl Sync for CacheWrapper {}
+
+unsafe impl Send for WriteBufferManagerWrapper {}
+unsafe impl Sync for WriteBufferManagerWrapper {}
+
+unsafe impl Send for RateLimiterWrapper {}
+unsafe impl Sync for RateLimiterWrapper {}
+
+unsafe impl Send for SstFileManagerWrapper {}
+unsafe impl Sync for SstFileManagerWrapper {}
+
+unsafe impl Send for SharedEnvWrapper {}
+unsafe impl Sync for SharedEnvWrapper {}
+
+unsafe impl Send for FaultInjectionFsWrapper {}
+unsafe impl Sync for FaultInjectionFsWrapper {}
+
+unsafe impl Send for StatisticsWrapper {}
+unsafe impl Sync for StatisticsWrapper {}
+
+unsafe impl Send for BackupEngineWrapper {}
+
+unsafe impl Send for SstFileWriterWrapper {}
+
+unsafe impl Send for SstFileReaderWrapper {}
+
+unsafe impl Send for TransactionLogIteratorWrapper {}
+
 unsafe impl Send for rocksdb::WriteBatch {}
 // Sync because mutable methods take Pin<&mut Self>.
 unsafe impl Sync for rocksdb::WriteBatch {}
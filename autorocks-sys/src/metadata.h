@@ -0,0 +1,238 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/metadata.h"
+#include "rocksdb/table_properties.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Autocxx cannot access fields of non-pod type, so copy them (borrowed) into
+// a pod.
+struct LiveFileMetaDataPod
+{
+    Slice column_family_name;
+    int32_t level;
+    Slice relative_filename;
+    Slice directory;
+    uint64_t size;
+    uint64_t smallest_seqno;
+    uint64_t largest_seqno;
+    Slice smallest_key;
+    Slice largest_key;
+    uint64_t num_entries;
+    uint64_t num_deletions;
+};
+
+inline LiveFileMetaDataPod live_file_metadata_to_pod(const LiveFileMetaData &m)
+{
+    return LiveFileMetaDataPod{
+        m.column_family_name,
+        m.level,
+        m.relative_filename,
+        m.directory,
+        m.size,
+        m.smallest_seqno,
+        m.largest_seqno,
+        m.smallestkey,
+        m.largestkey,
+        m.num_entries,
+        m.num_deletions,
+    };
+}
+
+inline LiveFileMetaData live_file_metadata_from_pod(const LiveFileMetaDataPod &p)
+{
+    LiveFileMetaData m;
+    m.column_family_name = p.column_family_name.ToString();
+    m.level = p.level;
+    m.relative_filename = p.relative_filename.ToString();
+    m.name = "/" + m.relative_filename;
+    m.directory = p.directory.ToString();
+    m.db_path = m.directory;
+    m.size = p.size;
+    m.smallest_seqno = p.smallest_seqno;
+    m.largest_seqno = p.largest_seqno;
+    m.smallestkey = p.smallest_key.ToString();
+    m.largestkey = p.largest_key.ToString();
+    m.num_entries = p.num_entries;
+    m.num_deletions = p.num_deletions;
+    return m;
+}
+
+inline LiveFileMetaDataPod sst_file_metadata_to_pod(const SstFileMetaData &m, Slice column_family_name, int32_t level)
+{
+    return LiveFileMetaDataPod{
+        column_family_name,
+        level,
+        m.relative_filename,
+        m.directory,
+        m.size,
+        m.smallest_seqno,
+        m.largest_seqno,
+        m.smallestkey,
+        m.largestkey,
+        m.num_entries,
+        m.num_deletions,
+    };
+}
+
+struct ColumnFamilyMetaDataWrapper
+{
+    ColumnFamilyMetaData inner;
+
+    Slice name() const
+    {
+        return inner.name;
+    }
+
+    uint64_t size() const
+    {
+        return inner.size;
+    }
+
+    size_t level_len() const
+    {
+        return inner.levels.size();
+    }
+
+    int32_t level(size_t level_index) const
+    {
+        return inner.levels[level_index].level;
+    }
+
+    uint64_t level_size(size_t level_index) const
+    {
+        return inner.levels[level_index].size;
+    }
+
+    size_t file_len(size_t level_index) const
+    {
+        return inner.levels[level_index].files.size();
+    }
+
+    LiveFileMetaDataPod file(size_t level_index, size_t index) const
+    {
+        const LevelMetaData &level = inner.levels[level_index];
+        return sst_file_metadata_to_pod(level.files[index], inner.name, level.level);
+    }
+};
+
+struct LiveFilesMetaDataWrapper
+{
+    vector<LiveFileMetaData> inner;
+
+    size_t len() const
+    {
+        return inner.size();
+    }
+
+    LiveFileMetaDataPod get(size_t index) const
+    {
+        return live_file_metadata_to_pod(inner[index]);
+    }
+};
+
+struct TablePropertiesPod
+{
+    uint64_t data_size;
+    uint64_t index_size;
+    uint64_t filter_size;
+    uint64_t raw_key_size;
+    uint64_t raw_value_size;
+    uint64_t num_data_blocks;
+    uint64_t num_entries;
+    uint64_t num_deletions;
+    uint64_t num_merge_operands;
+    uint64_t num_range_deletions;
+    uint64_t format_version;
+    uint64_t creation_time;
+    uint64_t oldest_key_time;
+    uint64_t file_creation_time;
+    Slice column_family_name;
+    Slice comparator_name;
+    Slice merge_operator_name;
+    Slice prefix_extractor_name;
+    Slice filter_policy_name;
+    Slice compression_name;
+};
+
+inline TablePropertiesPod table_properties_to_pod(const TableProperties &p)
+{
+    return TablePropertiesPod{
+        p.data_size,
+        p.index_size,
+        p.filter_size,
+        p.raw_key_size,
+        p.raw_value_size,
+        p.num_data_blocks,
+        p.num_entries,
+        p.num_deletions,
+        p.num_merge_operands,
+        p.num_range_deletions,
+        p.format_version,
+        p.creation_time,
+        p.oldest_key_time,
+        p.file_creation_time,
+        p.column_family_name,
+        p.comparator_name,
+        p.merge_operator_name,
+        p.prefix_extractor_name,
+        p.filter_policy_name,
+        p.compression_name,
+    };
+}
+
+struct ExportImportFilesMetaDataWrapper
+{
+    ExportImportFilesMetaData inner;
+
+    Slice db_comparator_name() const
+    {
+        return inner.db_comparator_name;
+    }
+
+    void set_db_comparator_name(Slice name)
+    {
+        inner.db_comparator_name = name.ToString();
+    }
+
+    size_t len() const
+    {
+        return inner.files.size();
+    }
+
+    LiveFileMetaDataPod get(size_t index) const
+    {
+        return live_file_metadata_to_pod(inner.files[index]);
+    }
+
+    void push(const LiveFileMetaDataPod &file)
+    {
+        inner.files.push_back(live_file_metadata_from_pod(file));
+    }
+};
+
+struct TablePropertiesCollectionWrapper
+{
+    vector<pair<string, shared_ptr<const TableProperties>>> inner;
+
+    size_t len() const
+    {
+        return inner.size();
+    }
+
+    Slice file_name(size_t index) const
+    {
+        return inner[index].first;
+    }
+
+    TablePropertiesPod get(size_t index) const
+    {
+        return table_properties_to_pod(*inner[index].second);
+    }
+};
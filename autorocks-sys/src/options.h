@@ -0,0 +1,265 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include "rocksdb/options.h"
+#include "rocksdb/table.h"
+#include "rocksdb/filter_policy.h"
+#include "rocksdb/comparator.h"
+#include "rocksdb/convenience.h"
+#include "cache.h"
+#include "slice_transform.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Timestamps of the u64 timestamp comparator are encoded as fixed 64 bit
+// little endian.
+inline string encode_u64_ts(uint64_t ts)
+{
+    string buf(sizeof(ts), '\0');
+    for (size_t i = 0; i < sizeof(ts); i++)
+    {
+        buf[i] = static_cast<char>(ts >> (8 * i));
+    }
+    return buf;
+}
+
+inline uint64_t decode_u64_ts(const Slice &buf)
+{
+    uint64_t ts = 0;
+    for (size_t i = 0; i < sizeof(ts) && i < buf.size(); i++)
+    {
+        ts |= static_cast<uint64_t>(static_cast<unsigned char>(buf[i])) << (8 * i);
+    }
+    return ts;
+}
+
+struct BlockBasedOptionsWrapper
+{
+    BlockBasedTableOptions options;
+
+    void set_block_size(uint64_t val)
+    {
+        options.block_size = val;
+    }
+
+    void set_metadata_block_size(uint64_t val)
+    {
+        options.metadata_block_size = val;
+    }
+
+    void set_format_version(uint32_t val)
+    {
+        options.format_version = val;
+    }
+
+    void set_cache_index_and_filter_blocks(bool val)
+    {
+        options.cache_index_and_filter_blocks = val;
+    }
+
+    void set_pin_l0_filter_and_index_blocks_in_cache(bool val)
+    {
+        options.pin_l0_filter_and_index_blocks_in_cache = val;
+    }
+
+    void set_pin_top_level_index_and_filter(bool val)
+    {
+        options.pin_top_level_index_and_filter = val;
+    }
+
+    void set_index_type(BlockBasedTableOptions::IndexType val)
+    {
+        options.index_type = val;
+    }
+
+    void set_whole_key_filtering(bool val)
+    {
+        options.whole_key_filtering = val;
+    }
+
+    void set_partition_filters(bool val)
+    {
+        options.partition_filters = val;
+    }
+
+    void set_no_block_cache(bool val)
+    {
+        options.no_block_cache = val;
+    }
+
+    void set_block_cache(const CacheWrapper &cache)
+    {
+        options.block_cache = cache.cache;
+    }
+
+    void set_bloom_filter(double bits_per_key)
+    {
+        options.filter_policy.reset(NewBloomFilterPolicy(bits_per_key));
+    }
+
+    void set_ribbon_filter(double bloom_equivalent_bits_per_key, int32_t bloom_before_level)
+    {
+        options.filter_policy.reset(NewRibbonFilterPolicy(bloom_equivalent_bits_per_key, bloom_before_level));
+    }
+};
+
+struct CfOptionsWrapper
+{
+    ColumnFamilyOptions options;
+
+    void set_write_buffer_size(size_t val)
+    {
+        options.write_buffer_size = val;
+    }
+
+    void set_max_write_buffer_number(int32_t val)
+    {
+        options.max_write_buffer_number = val;
+    }
+
+    void set_min_write_buffer_number_to_merge(int32_t val)
+    {
+        options.min_write_buffer_number_to_merge = val;
+    }
+
+    void set_target_file_size_base(uint64_t val)
+    {
+        options.target_file_size_base = val;
+    }
+
+    void set_target_file_size_multiplier(int32_t val)
+    {
+        options.target_file_size_multiplier = val;
+    }
+
+    void set_max_bytes_for_level_base(uint64_t val)
+    {
+        options.max_bytes_for_level_base = val;
+    }
+
+    void set_num_levels(int32_t val)
+    {
+        options.num_levels = val;
+    }
+
+    void set_level0_file_num_compaction_trigger(int32_t val)
+    {
+        options.level0_file_num_compaction_trigger = val;
+    }
+
+    void set_level0_slowdown_writes_trigger(int32_t val)
+    {
+        options.level0_slowdown_writes_trigger = val;
+    }
+
+    void set_level0_stop_writes_trigger(int32_t val)
+    {
+        options.level0_stop_writes_trigger = val;
+    }
+
+    void set_compaction_style(CompactionStyle val)
+    {
+        options.compaction_style = val;
+    }
+
+    void set_compression(CompressionType val)
+    {
+        options.compression = val;
+    }
+
+    void set_bottommost_compression(CompressionType val)
+    {
+        options.bottommost_compression = val;
+    }
+
+    void set_compression_per_level(CompressionType const *levels, size_t len)
+    {
+        options.compression_per_level.assign(levels, levels + len);
+    }
+
+    void set_ttl(uint64_t val)
+    {
+        options.ttl = val;
+    }
+
+    void set_periodic_compaction_seconds(uint64_t val)
+    {
+        options.periodic_compaction_seconds = val;
+    }
+
+    void set_prefix_extractor(const SliceTransformWrapper &prefix_extractor)
+    {
+        options.prefix_extractor = prefix_extractor.inner;
+    }
+
+    void set_memtable_prefix_bloom_size_ratio(double val)
+    {
+        options.memtable_prefix_bloom_size_ratio = val;
+    }
+
+    void set_memtable_whole_key_filtering(bool val)
+    {
+        options.memtable_whole_key_filtering = val;
+    }
+
+    void set_block_based_table_factory(const BlockBasedOptionsWrapper &table_options)
+    {
+        options.table_factory.reset(NewBlockBasedTableFactory(table_options.options));
+    }
+
+    // BytewiseComparatorWithU64Ts is not declared in the public headers, but
+    // it is registered by name.
+    Status set_u64_timestamp_comparator()
+    {
+        return Comparator::CreateFromString(ConfigOptions(), "leveldb.BytewiseComparator.u64ts", &options.comparator);
+    }
+
+    void set_enable_blob_files(bool val)
+    {
+        options.enable_blob_files = val;
+    }
+
+    void set_min_blob_size(uint64_t val)
+    {
+        options.min_blob_size = val;
+    }
+
+    void set_blob_file_size(uint64_t val)
+    {
+        options.blob_file_size = val;
+    }
+
+    void set_blob_compression_type(CompressionType val)
+    {
+        options.blob_compression_type = val;
+    }
+
+    void set_enable_blob_garbage_collection(bool val)
+    {
+        options.enable_blob_garbage_collection = val;
+    }
+
+    void set_blob_garbage_collection_age_cutoff(double val)
+    {
+        options.blob_garbage_collection_age_cutoff = val;
+    }
+
+    void set_blob_garbage_collection_force_threshold(double val)
+    {
+        options.blob_garbage_collection_force_threshold = val;
+    }
+
+    void set_blob_file_starting_level(int32_t val)
+    {
+        options.blob_file_starting_level = val;
+    }
+
+    void set_blob_cache(const CacheWrapper &cache)
+    {
+        options.blob_cache = cache.cache;
+    }
+};
@@ -0,0 +1,195 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include "rocksdb/perf_level.h"
+#include "rocksdb/perf_context.h"
+#include "rocksdb/iostats_context.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Perf and IO stats contexts are thread local. These functions access the
+// contexts of the calling thread.
+
+void set_perf_level(PerfLevel level)
+{
+    SetPerfLevel(level);
+}
+
+PerfLevel get_perf_level()
+{
+    return GetPerfLevel();
+}
+
+struct PerfContextPod
+{
+    uint64_t user_key_comparison_count;
+    uint64_t block_cache_hit_count;
+    uint64_t block_read_count;
+    uint64_t block_read_byte;
+    uint64_t block_read_time;
+    uint64_t block_cache_index_hit_count;
+    uint64_t index_block_read_count;
+    uint64_t block_cache_filter_hit_count;
+    uint64_t filter_block_read_count;
+    uint64_t block_checksum_time;
+    uint64_t block_decompress_time;
+    uint64_t get_read_bytes;
+    uint64_t multiget_read_bytes;
+    uint64_t iter_read_bytes;
+    uint64_t internal_key_skipped_count;
+    uint64_t internal_delete_skipped_count;
+    uint64_t internal_recent_skipped_count;
+    uint64_t internal_merge_count;
+    uint64_t get_snapshot_time;
+    uint64_t get_from_memtable_time;
+    uint64_t get_from_memtable_count;
+    uint64_t get_post_process_time;
+    uint64_t get_from_output_files_time;
+    uint64_t seek_on_memtable_time;
+    uint64_t seek_on_memtable_count;
+    uint64_t next_on_memtable_count;
+    uint64_t prev_on_memtable_count;
+    uint64_t seek_child_seek_time;
+    uint64_t seek_child_seek_count;
+    uint64_t seek_min_heap_time;
+    uint64_t seek_max_heap_time;
+    uint64_t seek_internal_seek_time;
+    uint64_t find_next_user_entry_time;
+    uint64_t write_wal_time;
+    uint64_t write_memtable_time;
+    uint64_t write_delay_time;
+    uint64_t write_scheduling_flushes_compactions_time;
+    uint64_t write_pre_and_post_process_time;
+    uint64_t write_thread_wait_nanos;
+    uint64_t db_mutex_lock_nanos;
+    uint64_t db_condition_wait_nanos;
+    uint64_t read_index_block_nanos;
+    uint64_t read_filter_block_nanos;
+    uint64_t new_table_block_iter_nanos;
+    uint64_t new_table_iterator_nanos;
+    uint64_t block_seek_nanos;
+    uint64_t find_table_nanos;
+    uint64_t bloom_memtable_hit_count;
+    uint64_t bloom_memtable_miss_count;
+    uint64_t bloom_sst_hit_count;
+    uint64_t bloom_sst_miss_count;
+    uint64_t key_lock_wait_time;
+    uint64_t key_lock_wait_count;
+    uint64_t get_cpu_nanos;
+    uint64_t iter_next_cpu_nanos;
+    uint64_t iter_prev_cpu_nanos;
+    uint64_t iter_seek_cpu_nanos;
+};
+
+PerfContextPod perf_context_snapshot()
+{
+    const PerfContext *ctx = get_perf_context();
+    return PerfContextPod{
+        ctx->user_key_comparison_count,
+        ctx->block_cache_hit_count,
+        ctx->block_read_count,
+        ctx->block_read_byte,
+        ctx->block_read_time,
+        ctx->block_cache_index_hit_count,
+        ctx->index_block_read_count,
+        ctx->block_cache_filter_hit_count,
+        ctx->filter_block_read_count,
+        ctx->block_checksum_time,
+        ctx->block_decompress_time,
+        ctx->get_read_bytes,
+        ctx->multiget_read_bytes,
+        ctx->iter_read_bytes,
+        ctx->internal_key_skipped_count,
+        ctx->internal_delete_skipped_count,
+        ctx->internal_recent_skipped_count,
+        ctx->internal_merge_count,
+        ctx->get_snapshot_time,
+        ctx->get_from_memtable_time,
+        ctx->get_from_memtable_count,
+        ctx->get_post_process_time,
+        ctx->get_from_output_files_time,
+        ctx->seek_on_memtable_time,
+        ctx->seek_on_memtable_count,
+        ctx->next_on_memtable_count,
+        ctx->prev_on_memtable_count,
+        ctx->seek_child_seek_time,
+        ctx->seek_child_seek_count,
+        ctx->seek_min_heap_time,
+        ctx->seek_max_heap_time,
+        ctx->seek_internal_seek_time,
+        ctx->find_next_user_entry_time,
+        ctx->write_wal_time,
+        ctx->write_memtable_time,
+        ctx->write_delay_time,
+        ctx->write_scheduling_flushes_compactions_time,
+        ctx->write_pre_and_post_process_time,
+        ctx->write_thread_wait_nanos,
+        ctx->db_mutex_lock_nanos,
+        ctx->db_condition_wait_nanos,
+        ctx->read_index_block_nanos,
+        ctx->read_filter_block_nanos,
+        ctx->new_table_block_iter_nanos,
+        ctx->new_table_iterator_nanos,
+        ctx->block_seek_nanos,
+        ctx->find_table_nanos,
+        ctx->bloom_memtable_hit_count,
+        ctx->bloom_memtable_miss_count,
+        ctx->bloom_sst_hit_count,
+        ctx->bloom_sst_miss_count,
+        ctx->key_lock_wait_time,
+        ctx->key_lock_wait_count,
+        ctx->get_cpu_nanos,
+        ctx->iter_next_cpu_nanos,
+        ctx->iter_prev_cpu_nanos,
+        ctx->iter_seek_cpu_nanos,
+    };
+}
+
+void reset_perf_context()
+{
+    get_perf_context()->Reset();
+}
+
+struct IOStatsContextPod
+{
+    uint64_t bytes_written;
+    uint64_t bytes_read;
+    uint64_t open_nanos;
+    uint64_t allocate_nanos;
+    uint64_t write_nanos;
+    uint64_t read_nanos;
+    uint64_t range_sync_nanos;
+    uint64_t fsync_nanos;
+    uint64_t prepare_write_nanos;
+    uint64_t logger_nanos;
+    uint64_t cpu_write_nanos;
+    uint64_t cpu_read_nanos;
+};
+
+IOStatsContextPod iostats_context_snapshot()
+{
+    const IOStatsContext *ctx = get_iostats_context();
+    return IOStatsContextPod{
+        ctx->bytes_written,
+        ctx->bytes_read,
+        ctx->open_nanos,
+        ctx->allocate_nanos,
+        ctx->write_nanos,
+        ctx->read_nanos,
+        ctx->range_sync_nanos,
+        ctx->fsync_nanos,
+        ctx->prepare_write_nanos,
+        ctx->logger_nanos,
+        ctx->cpu_write_nanos,
+        ctx->cpu_read_nanos,
+    };
+}
+
+void reset_iostats_context()
+{
+    get_iostats_context()->Reset();
+}
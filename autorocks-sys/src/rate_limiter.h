@@ -0,0 +1,47 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/rate_limiter.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Note: make sure it's Unpin.
+struct RateLimiterWrapper
+{
+    shared_ptr<RateLimiter> limiter;
+
+    void set_bytes_per_second(int64_t bytes_per_second) const
+    {
+        limiter->SetBytesPerSecond(bytes_per_second);
+    }
+
+    int64_t bytes_per_second() const
+    {
+        return limiter->GetBytesPerSecond();
+    }
+
+    int64_t single_burst_bytes() const
+    {
+        return limiter->GetSingleBurstBytes();
+    }
+
+    int64_t total_bytes_through(Env::IOPriority pri) const
+    {
+        return limiter->GetTotalBytesThrough(pri);
+    }
+
+    int64_t total_requests(Env::IOPriority pri) const
+    {
+        return limiter->GetTotalRequests(pri);
+    }
+};
+
+RateLimiterWrapper new_rate_limiter(int64_t rate_bytes_per_sec, int64_t refill_period_us, int32_t fairness, RateLimiter::Mode mode, bool auto_tuned)
+{
+    return RateLimiterWrapper{shared_ptr<RateLimiter>(NewGenericRateLimiter(rate_bytes_per_sec, refill_period_us, fairness, mode, auto_tuned))};
+}
@@ -0,0 +1,79 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/slice_transform.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Need this because autocxx cannot handle `shared_ptr<const Foo>`.
+// Note: make sure it's Unpin.
+struct SliceTransformWrapper
+{
+    shared_ptr<const SliceTransform> inner;
+};
+
+// A SliceTransform implemented in rust.
+//
+// Autocxx cannot handle function pointers, so they are passed around as
+// `void *`.
+struct RustSliceTransform : SliceTransform
+{
+    typedef Slice (*TransformFn)(void *, Slice);
+    typedef bool (*InDomainFn)(void *, Slice);
+    typedef void (*DropFn)(void *);
+
+    string name;
+    void *ctx;
+    TransformFn transform_fn;
+    InDomainFn in_domain_fn;
+    DropFn drop_fn;
+
+    RustSliceTransform(Slice name_, void *ctx_, void *transform_fn_, void *in_domain_fn_, void *drop_fn_)
+        : name(name_.ToString()),
+          ctx(ctx_),
+          transform_fn(reinterpret_cast<TransformFn>(transform_fn_)),
+          in_domain_fn(reinterpret_cast<InDomainFn>(in_domain_fn_)),
+          drop_fn(reinterpret_cast<DropFn>(drop_fn_))
+    {
+    }
+
+    ~RustSliceTransform()
+    {
+        drop_fn(ctx);
+    }
+
+    const char *Name() const override
+    {
+        return name.c_str();
+    }
+
+    Slice Transform(const Slice &key) const override
+    {
+        return transform_fn(ctx, key);
+    }
+
+    bool InDomain(const Slice &key) const override
+    {
+        return in_domain_fn(ctx, key);
+    }
+};
+
+SliceTransformWrapper new_fixed_prefix_transform(size_t prefix_len)
+{
+    return SliceTransformWrapper{shared_ptr<const SliceTransform>(NewFixedPrefixTransform(prefix_len))};
+}
+
+SliceTransformWrapper new_capped_prefix_transform(size_t cap_len)
+{
+    return SliceTransformWrapper{shared_ptr<const SliceTransform>(NewCappedPrefixTransform(cap_len))};
+}
+
+SliceTransformWrapper new_rust_slice_transform(Slice name, void *ctx, void *transform_fn, void *in_domain_fn, void *drop_fn)
+{
+    return SliceTransformWrapper{make_shared<RustSliceTransform>(name, ctx, transform_fn, in_domain_fn, drop_fn)};
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/options.h"
+#include "rocksdb/sst_file_reader.h"
+#include "rocksdb/sst_file_writer.h"
+#include "options.h"
+#include "metadata.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Autocxx cannot access fields of non-pod type, so copy them (borrowed) into
+// a pod.
+struct ExternalSstFileInfoPod
+{
+    Slice file_path;
+    Slice smallest_key;
+    Slice largest_key;
+    Slice smallest_range_del_key;
+    Slice largest_range_del_key;
+    uint64_t sequence_number;
+    uint64_t file_size;
+    uint64_t num_entries;
+    uint64_t num_range_del_entries;
+    int32_t version;
+};
+
+struct SstFileWriterWrapper
+{
+    unique_ptr<SstFileWriter> writer;
+    ExternalSstFileInfo info;
+
+    Status open(const CfOptionsWrapper &options, Slice file_path)
+    {
+        writer = make_unique<SstFileWriter>(EnvOptions(), Options(DBOptions(), options.options));
+        return writer->Open(file_path.ToString());
+    }
+
+    Status put(const Slice &key, const Slice &value)
+    {
+        return writer->Put(key, value);
+    }
+
+    Status merge(const Slice &key, const Slice &value)
+    {
+        return writer->Merge(key, value);
+    }
+
+    Status del(const Slice &key)
+    {
+        return writer->Delete(key);
+    }
+
+    Status delete_range(const Slice &begin_key, const Slice &end_key)
+    {
+        return writer->DeleteRange(begin_key, end_key);
+    }
+
+    Status finish()
+    {
+        return writer->Finish(&info);
+    }
+
+    uint64_t file_size() const
+    {
+        return writer->FileSize();
+    }
+
+    ExternalSstFileInfoPod info_pod() const
+    {
+        return ExternalSstFileInfoPod{
+            info.file_path,
+            info.smallest_key,
+            info.largest_key,
+            info.smallest_range_del_key,
+            info.largest_range_del_key,
+            info.sequence_number,
+            info.file_size,
+            info.num_entries,
+            info.num_range_del_entries,
+            info.version,
+        };
+    }
+};
+
+struct IngestOptionsWrapper
+{
+    IngestExternalFileOptions options;
+
+    void set_move_files(bool val)
+    {
+        options.move_files = val;
+    }
+
+    void set_snapshot_consistency(bool val)
+    {
+        options.snapshot_consistency = val;
+    }
+
+    void set_allow_global_seqno(bool val)
+    {
+        options.allow_global_seqno = val;
+    }
+
+    void set_allow_blocking_flush(bool val)
+    {
+        options.allow_blocking_flush = val;
+    }
+
+    void set_ingest_behind(bool val)
+    {
+        options.ingest_behind = val;
+    }
+
+    void set_verify_checksums_before_ingest(bool val)
+    {
+        options.verify_checksums_before_ingest = val;
+    }
+};
+
+struct SstFileReaderWrapper
+{
+    unique_ptr<SstFileReader> reader;
+    shared_ptr<const TableProperties> properties;
+
+    Status open(const CfOptionsWrapper &options, Slice file_path)
+    {
+        reader = make_unique<SstFileReader>(Options(DBOptions(), options.options));
+        Status status = reader->Open(file_path.ToString());
+        if (status.ok())
+        {
+            properties = reader->GetTableProperties();
+        }
+        return status;
+    }
+
+    unique_ptr<Iterator> iter(const ReadOptions &options) const
+    {
+        return unique_ptr<Iterator>(reader->NewIterator(options));
+    }
+
+    TablePropertiesPod table_properties() const
+    {
+        return table_properties_to_pod(*properties);
+    }
+
+    Status verify_checksum() const
+    {
+        return reader->VerifyChecksum();
+    }
+};
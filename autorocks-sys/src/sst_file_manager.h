@@ -0,0 +1,105 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include <vector>
+#include "rocksdb/env.h"
+#include "rocksdb/sst_file_manager.h"
+#include "env.h"
+
+using namespace std;
+using namespace rocksdb;
+
+struct TrackedFilesWrapper
+{
+    vector<pair<string, uint64_t>> files;
+
+    size_t len() const
+    {
+        return files.size();
+    }
+
+    Slice path(size_t i) const
+    {
+        return files[i].first;
+    }
+
+    uint64_t size(size_t i) const
+    {
+        return files[i].second;
+    }
+};
+
+// Note: make sure it's Unpin.
+struct SstFileManagerWrapper
+{
+    shared_ptr<SstFileManager> manager;
+    // The manager only keeps a raw pointer to the env.
+    shared_ptr<Env> env;
+
+    void set_max_allowed_space_usage(uint64_t max_allowed_space) const
+    {
+        manager->SetMaxAllowedSpaceUsage(max_allowed_space);
+    }
+
+    void set_compaction_buffer_size(uint64_t compaction_buffer_size) const
+    {
+        manager->SetCompactionBufferSize(compaction_buffer_size);
+    }
+
+    bool is_max_allowed_space_reached() const
+    {
+        return manager->IsMaxAllowedSpaceReached();
+    }
+
+    bool is_max_allowed_space_reached_including_compactions() const
+    {
+        return manager->IsMaxAllowedSpaceReachedIncludingCompactions();
+    }
+
+    uint64_t total_size() const
+    {
+        return manager->GetTotalSize();
+    }
+
+    void tracked_files(TrackedFilesWrapper *out) const
+    {
+        auto files = manager->GetTrackedFiles();
+        out->files.assign(files.begin(), files.end());
+    }
+
+    int64_t delete_rate_bytes_per_sec() const
+    {
+        return manager->GetDeleteRateBytesPerSecond();
+    }
+
+    void set_delete_rate_bytes_per_sec(int64_t delete_rate) const
+    {
+        manager->SetDeleteRateBytesPerSecond(delete_rate);
+    }
+
+    double max_trash_db_ratio() const
+    {
+        return manager->GetMaxTrashDBRatio();
+    }
+
+    void set_max_trash_db_ratio(double ratio) const
+    {
+        manager->SetMaxTrashDBRatio(ratio);
+    }
+
+    uint64_t total_trash_size() const
+    {
+        return manager->GetTotalTrashSize();
+    }
+};
+
+SstFileManagerWrapper new_sst_file_manager(const SharedEnvWrapper &env, int64_t delete_rate_bytes_per_sec)
+{
+    return SstFileManagerWrapper{
+        shared_ptr<SstFileManager>(NewSstFileManager(env.env.get(), nullptr, "", delete_rate_bytes_per_sec)),
+        env.env};
+}
@@ -0,0 +1,54 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/statistics.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Note: make sure StatisticsWrapper is Unpin.
+struct StatisticsWrapper
+{
+    shared_ptr<Statistics> inner;
+
+    uint64_t ticker(Tickers ticker) const
+    {
+        return inner->getTickerCount(ticker);
+    }
+
+    HistogramData histogram(Histograms histogram) const
+    {
+        HistogramData data;
+        inner->histogramData(histogram, &data);
+        return data;
+    }
+
+    Status reset() const
+    {
+        return inner->Reset();
+    }
+
+    StatsLevel stats_level() const
+    {
+        return inner->get_stats_level();
+    }
+
+    void set_stats_level(StatsLevel level) const
+    {
+        inner->set_stats_level(level);
+    }
+
+    unique_ptr<string> dump() const
+    {
+        return make_unique<string>(inner->ToString());
+    }
+};
+
+StatisticsWrapper new_statistics()
+{
+    return StatisticsWrapper{CreateDBStatistics()};
+}
@@ -0,0 +1,57 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include "rocksdb/iterator.h"
+#include "rocksdb/wide_columns.h"
+
+using namespace std;
+using namespace rocksdb;
+
+inline WideColumns to_wide_columns(Slice const *names, Slice const *values, size_t len)
+{
+    WideColumns columns;
+    columns.reserve(len);
+    for (size_t i = 0; i < len; i++)
+    {
+        columns.emplace_back(names[i], values[i]);
+    }
+    return columns;
+}
+
+// Borrowed from the columns it was read from.
+struct WideColumnPod
+{
+    Slice name;
+    Slice value;
+};
+
+struct PinnableWideColumnsWrapper
+{
+    PinnableWideColumns inner;
+
+    size_t len() const
+    {
+        return inner.columns().size();
+    }
+
+    WideColumnPod get(size_t index) const
+    {
+        const WideColumn &column = inner.columns()[index];
+        return WideColumnPod{column.name(), column.value()};
+    }
+};
+
+// Only valid until the iterator is moved.
+size_t iterator_columns_len(const Iterator &iter)
+{
+    return iter.columns().size();
+}
+
+WideColumnPod iterator_column(const Iterator &iter, size_t index)
+{
+    const WideColumn &column = iter.columns()[index];
+    return WideColumnPod{column.name(), column.value()};
+}
@@ -0,0 +1,213 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/write_batch.h"
+#include "rocksdb/transaction_log.h"
+#include "wide_columns.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Callbacks implemented in rust. Each fn takes ctx as the first argument.
+// Single deletes are reported as deletes.
+struct RustWriteBatchHandler : WriteBatch::Handler
+{
+    typedef void (*PutFn)(void *, uint32_t, Slice, Slice);
+    typedef void (*DeleteFn)(void *, uint32_t, Slice);
+    typedef void (*DeleteRangeFn)(void *, uint32_t, Slice, Slice);
+    typedef void (*MergeFn)(void *, uint32_t, Slice, Slice);
+    typedef void (*LogDataFn)(void *, Slice);
+    typedef void (*PutEntityFn)(void *, uint32_t, Slice, WideColumnPod const *, size_t);
+
+    void *ctx;
+    PutFn put_fn;
+    DeleteFn delete_fn;
+    DeleteRangeFn delete_range_fn;
+    MergeFn merge_fn;
+    LogDataFn log_data_fn;
+    PutEntityFn put_entity_fn;
+
+    RustWriteBatchHandler(void *ctx_, void *put_fn_, void *delete_fn_, void *delete_range_fn_, void *merge_fn_, void *log_data_fn_, void *put_entity_fn_)
+        : ctx(ctx_),
+          put_fn(reinterpret_cast<PutFn>(put_fn_)),
+          delete_fn(reinterpret_cast<DeleteFn>(delete_fn_)),
+          delete_range_fn(reinterpret_cast<DeleteRangeFn>(delete_range_fn_)),
+          merge_fn(reinterpret_cast<MergeFn>(merge_fn_)),
+          log_data_fn(reinterpret_cast<LogDataFn>(log_data_fn_)),
+          put_entity_fn(reinterpret_cast<PutEntityFn>(put_entity_fn_))
+    {
+    }
+
+    Status PutCF(uint32_t column_family_id, const Slice &key, const Slice &value) override
+    {
+        put_fn(ctx, column_family_id, key, value);
+        return Status::OK();
+    }
+
+    Status DeleteCF(uint32_t column_family_id, const Slice &key) override
+    {
+        delete_fn(ctx, column_family_id, key);
+        return Status::OK();
+    }
+
+    Status SingleDeleteCF(uint32_t column_family_id, const Slice &key) override
+    {
+        delete_fn(ctx, column_family_id, key);
+        return Status::OK();
+    }
+
+    Status DeleteRangeCF(uint32_t column_family_id, const Slice &begin_key, const Slice &end_key) override
+    {
+        delete_range_fn(ctx, column_family_id, begin_key, end_key);
+        return Status::OK();
+    }
+
+    Status MergeCF(uint32_t column_family_id, const Slice &key, const Slice &value) override
+    {
+        merge_fn(ctx, column_family_id, key, value);
+        return Status::OK();
+    }
+
+    Status PutEntityCF(uint32_t column_family_id, const Slice &key, const Slice &entity) override
+    {
+        PinnableWideColumns columns;
+        Status status = columns.SetWideColumnValue(entity);
+        if (!status.ok())
+        {
+            return status;
+        }
+        vector<WideColumnPod> pods;
+        pods.reserve(columns.columns().size());
+        for (const auto &column : columns.columns())
+        {
+            pods.push_back(WideColumnPod{column.name(), column.value()});
+        }
+        put_entity_fn(ctx, column_family_id, key, pods.data(), pods.size());
+        return Status::OK();
+    }
+
+    void LogData(const Slice &blob) override
+    {
+        log_data_fn(ctx, blob);
+    }
+
+    // Transaction markers carry no data.
+
+    Status MarkBeginPrepare(bool) override
+    {
+        return Status::OK();
+    }
+
+    Status MarkEndPrepare(const Slice &) override
+    {
+        return Status::OK();
+    }
+
+    Status MarkNoop(bool) override
+    {
+        return Status::OK();
+    }
+
+    Status MarkRollback(const Slice &) override
+    {
+        return Status::OK();
+    }
+
+    Status MarkCommit(const Slice &) override
+    {
+        return Status::OK();
+    }
+
+    Status MarkCommitWithTimestamp(const Slice &, const Slice &) override
+    {
+        return Status::OK();
+    }
+};
+
+Status iterate_write_batch(
+    const WriteBatch &batch,
+    void *ctx,
+    void *put_fn,
+    void *delete_fn,
+    void *delete_range_fn,
+    void *merge_fn,
+    void *log_data_fn,
+    void *put_entity_fn)
+{
+    RustWriteBatchHandler handler(ctx, put_fn, delete_fn, delete_range_fn, merge_fn, log_data_fn, put_entity_fn);
+    return batch.Iterate(&handler);
+}
+
+// Keys written by a batch, to lock them. Range deletions are not supported,
+// as with TransactionDB::Write.
+struct BatchKeyCollector : WriteBatch::Handler
+{
+    vector<pair<uint32_t, string>> keys;
+
+    Status PutCF(uint32_t column_family_id, const Slice &key, const Slice &) override
+    {
+        keys.emplace_back(column_family_id, key.ToString());
+        return Status::OK();
+    }
+
+    Status DeleteCF(uint32_t column_family_id, const Slice &key) override
+    {
+        keys.emplace_back(column_family_id, key.ToString());
+        return Status::OK();
+    }
+
+    Status SingleDeleteCF(uint32_t column_family_id, const Slice &key) override
+    {
+        keys.emplace_back(column_family_id, key.ToString());
+        return Status::OK();
+    }
+
+    Status MergeCF(uint32_t column_family_id, const Slice &key, const Slice &) override
+    {
+        keys.emplace_back(column_family_id, key.ToString());
+        return Status::OK();
+    }
+
+    Status PutEntityCF(uint32_t column_family_id, const Slice &key, const Slice &) override
+    {
+        keys.emplace_back(column_family_id, key.ToString());
+        return Status::OK();
+    }
+};
+
+Status write_batch_put_entity(WriteBatch &batch, ColumnFamilyHandle *cf, const Slice &key, Slice const *names, Slice const *values, size_t len)
+{
+    return batch.PutEntity(cf, key, to_wide_columns(names, values, len));
+}
+
+struct TransactionLogIteratorWrapper
+{
+    unique_ptr<TransactionLogIterator> inner;
+
+    bool valid() const
+    {
+        return inner->Valid();
+    }
+
+    void next()
+    {
+        inner->Next();
+    }
+
+    Status status() const
+    {
+        return inner->status();
+    }
+
+    // Can only be called once per position.
+    unique_ptr<WriteBatch> take_batch(uint64_t *sequence)
+    {
+        BatchResult result = inner->GetBatch();
+        *sequence = result.sequence;
+        return move(result.writeBatchPtr);
+    }
+};
@@ -0,0 +1,69 @@
+/*
+ * Copyright 2022, The Cozo Project Authors. Licensed under MIT/Apache-2.0/BSD-3-Clause.
+ */
+
+#pragma once
+
+#include <memory>
+#include "rocksdb/write_buffer_manager.h"
+#include "cache.h"
+
+using namespace std;
+using namespace rocksdb;
+
+// Note: make sure it's Unpin.
+struct WriteBufferManagerWrapper
+{
+    shared_ptr<WriteBufferManager> manager;
+
+    bool enabled() const
+    {
+        return manager->enabled();
+    }
+
+    bool cost_to_cache() const
+    {
+        return manager->cost_to_cache();
+    }
+
+    size_t memory_usage() const
+    {
+        return manager->memory_usage();
+    }
+
+    size_t mutable_memtable_memory_usage() const
+    {
+        return manager->mutable_memtable_memory_usage();
+    }
+
+    size_t dummy_entries_in_cache_usage() const
+    {
+        return manager->dummy_entries_in_cache_usage();
+    }
+
+    size_t buffer_size() const
+    {
+        return manager->buffer_size();
+    }
+
+    void set_buffer_size(size_t new_size) const
+    {
+        manager->SetBufferSize(new_size);
+    }
+
+    bool is_stall_active() const
+    {
+        return manager->IsStallActive();
+    }
+};
+
+// cache can be null.
+WriteBufferManagerWrapper new_write_buffer_manager(size_t buffer_size, const CacheWrapper *cache, bool allow_stall)
+{
+    shared_ptr<Cache> c;
+    if (cache)
+    {
+        c = cache->cache;
+    }
+    return WriteBufferManagerWrapper{make_shared<WriteBufferManager>(buffer_size, c, allow_stall)};
+}
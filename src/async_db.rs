@@ -0,0 +1,307 @@
+use std::{
+    collections::VecDeque,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use autorocks_sys::rocksdb::PinnableSlice;
+use moveit::moveit;
+
+use crate::{Result, Transaction, TransactionDb, WriteBatch};
+
+/// Runs `TransactionDb` operations on a dedicated thread pool, so they can be
+/// awaited without blocking the async executor. Works with any executor.
+///
+/// Keys and values are copied into owned buffers, and results are returned
+/// in owned buffers.
+///
+/// Operations are queued when the returned future is created, and run even
+/// if it is never polled. Dropping the future cancels the operation only if
+/// it has not started yet, so a dropped write may or may not be applied,
+/// but it is never partially applied.
+///
+/// At most `queue_capacity` operations wait in the queue. Operations created
+/// while it is full are queued when their future is polled and there is
+/// room, so callers wait without blocking the executor.
+///
+/// Clones share the thread pool. The threads exit once all clones are
+/// dropped and the queued operations are done. Dropping doesn't wait for
+/// them, so it never blocks the executor, but queued operations keep the
+/// database open until they finish.
+#[derive(Clone)]
+pub struct AsyncDb {
+    db: TransactionDb,
+    pool: Arc<ThreadPool>,
+}
+
+impl AsyncDb {
+    /// Run operations on `threads` threads, with a queue capacity of 1024.
+    ///
+    /// # Panics
+    ///
+    /// If `threads` is 0.
+    pub fn new(db: TransactionDb, threads: usize) -> Self {
+        Self::with_queue_capacity(db, threads, 1024)
+    }
+
+    /// Run operations on `threads` threads, queueing at most
+    /// `queue_capacity` operations.
+    ///
+    /// # Panics
+    ///
+    /// If `threads` or `queue_capacity` is 0.
+    pub fn with_queue_capacity(db: TransactionDb, threads: usize, queue_capacity: usize) -> Self {
+        assert!(threads > 0);
+        assert!(queue_capacity > 0);
+        Self {
+            db,
+            pool: Arc::new(ThreadPool::new(threads, queue_capacity)),
+        }
+    }
+
+    /// The underlying database, for blocking operations.
+    pub fn db(&self) -> &TransactionDb {
+        &self.db
+    }
+
+    pub fn get(&self, col: usize, key: &[u8]) -> AsyncTask<Result<Option<Vec<u8>>>> {
+        let db = self.db.clone();
+        let key = key.to_vec();
+        self.pool.spawn(move || get(&db, col, &key))
+    }
+
+    /// Get several keys of a column family in one operation. All keys are
+    /// read from the same snapshot.
+    pub fn multi_get(
+        &self,
+        col: usize,
+        keys: Vec<Vec<u8>>,
+    ) -> AsyncTask<Result<Vec<Option<Vec<u8>>>>> {
+        let db = self.db.clone();
+        self.pool.spawn(move || {
+            let snapshot = db.snapshot();
+            moveit! {
+                let mut slice = PinnableSlice::new();
+            }
+            keys.iter()
+                .map(|key| Ok(snapshot.get(col, key, slice.as_mut())?.map(|v| v.to_vec())))
+                .collect()
+        })
+    }
+
+    pub fn put(&self, col: usize, key: &[u8], value: &[u8]) -> AsyncTask<Result<()>> {
+        let db = self.db.clone();
+        let key = key.to_vec();
+        let value = value.to_vec();
+        self.pool.spawn(move || db.put(col, &key, &value))
+    }
+
+    pub fn delete(&self, col: usize, key: &[u8]) -> AsyncTask<Result<()>> {
+        let db = self.db.clone();
+        let key = key.to_vec();
+        self.pool.spawn(move || db.delete(col, &key))
+    }
+
+    /// Apply `updates` atomically. Create it with `db().new_write_batch()`.
+    pub fn write(&self, mut updates: WriteBatch) -> AsyncTask<Result<()>> {
+        let db = self.db.clone();
+        self.pool.spawn(move || db.write(&mut updates))
+    }
+
+    /// Commit a transaction, e.g. one created with `db().begin_transaction()`.
+    pub fn commit(&self, mut transaction: Transaction) -> AsyncTask<Result<()>> {
+        self.pool.spawn(move || transaction.commit())
+    }
+}
+
+fn get(db: &TransactionDb, col: usize, key: &[u8]) -> Result<Option<Vec<u8>>> {
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    Ok(db.get(col, key, slice.as_mut())?.map(|v| v.to_vec()))
+}
+
+/// Result of an `AsyncDb` operation.
+///
+/// If the operation panics, the panic is resumed when this is polled.
+///
+/// # Panics
+///
+/// If polled again after it returned `Poll::Ready`.
+pub struct AsyncTask<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+    // The job, if the queue was full. Holding the pool keeps its threads
+    // running until the job is queued.
+    pending: Option<(Arc<ThreadPool>, Job)>,
+    done: bool,
+}
+
+struct Slot<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+    cancelled: bool,
+}
+
+impl<T> Future for AsyncTask<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        assert!(!self.done, "AsyncTask polled after completion");
+        if let Some((pool, job)) = self.pending.take() {
+            if let Err(job) = pool.try_push(job, Some(cx.waker())) {
+                self.pending = Some((pool, job));
+                return Poll::Pending;
+            }
+        }
+        let result = {
+            let mut slot = self.slot.lock().unwrap();
+            match slot.result.take() {
+                Some(result) => result,
+                None => {
+                    slot.waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+        };
+        self.done = true;
+        match result {
+            Ok(v) => Poll::Ready(v),
+            Err(panic) => panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl<T> Drop for AsyncTask<T> {
+    fn drop(&mut self) {
+        if let Ok(mut slot) = self.slot.lock() {
+            slot.cancelled = true;
+        }
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The threads are detached. They exit once the pool is dropped and the
+/// queue is drained.
+struct ThreadPool {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    job_added: Condvar,
+}
+
+struct Queue {
+    jobs: VecDeque<Job>,
+    capacity: usize,
+    /// Tasks waiting for room in the queue.
+    waiting: Vec<Waker>,
+    closed: bool,
+}
+
+impl ThreadPool {
+    fn new(threads: usize, queue_capacity: usize) -> Self {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue {
+                jobs: VecDeque::with_capacity(queue_capacity),
+                capacity: queue_capacity,
+                waiting: Vec::new(),
+                closed: false,
+            }),
+            job_added: Condvar::new(),
+        });
+        for i in 0..threads {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name(format!("autorocks-async-{i}"))
+                .spawn(move || loop {
+                    let (job, waiting) = {
+                        let mut queue = shared.queue.lock().unwrap();
+                        loop {
+                            if let Some(job) = queue.jobs.pop_front() {
+                                break (job, std::mem::take(&mut queue.waiting));
+                            }
+                            if queue.closed {
+                                return;
+                            }
+                            queue = shared.job_added.wait(queue).unwrap();
+                        }
+                    };
+                    // Don't hold the lock while waking tasks or running the
+                    // job.
+                    for waker in waiting {
+                        waker.wake();
+                    }
+                    job();
+                })
+                .expect("failed to spawn thread");
+        }
+        Self { shared }
+    }
+
+    /// Queue `job` if there is room. Otherwise return it, and wake `waker`
+    /// once there may be room.
+    fn try_push(&self, job: Job, waker: Option<&Waker>) -> Result<(), Job> {
+        let mut queue = self.shared.queue.lock().unwrap();
+        if queue.jobs.len() < queue.capacity {
+            queue.jobs.push_back(job);
+            drop(queue);
+            self.shared.job_added.notify_one();
+            Ok(())
+        } else {
+            if let Some(waker) = waker {
+                queue.waiting.push(waker.clone());
+            }
+            Err(job)
+        }
+    }
+
+    fn spawn<T, F>(self: &Arc<Self>, f: F) -> AsyncTask<Result<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        let slot = Arc::new(Mutex::new(Slot {
+            result: None,
+            waker: None,
+            cancelled: false,
+        }));
+        let job_slot = slot.clone();
+        let job: Job = Box::new(move || {
+            if job_slot.lock().unwrap().cancelled {
+                return;
+            }
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            let waker = {
+                let mut slot = job_slot.lock().unwrap();
+                slot.result = Some(result);
+                slot.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        let pending = self
+            .try_push(job, None)
+            .err()
+            .map(|job| (self.clone(), job));
+        AsyncTask {
+            slot,
+            pending,
+            done: false,
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // Threads exit once the queue is drained.
+        self.shared.queue.lock().unwrap().closed = true;
+        self.shared.job_added.notify_all();
+    }
+}
@@ -0,0 +1,170 @@
+use std::{os::unix::prelude::OsStrExt, path::Path, pin::Pin, ptr};
+
+use autocxx::c_void;
+use autorocks_sys::{BackupEngineWrapper, BackupInfoPod, BackupInfosWrapper};
+use moveit::{moveit, Emplace};
+
+use crate::{into_result, slice::as_rust_slice1, Result, TransactionDb};
+
+/// Information about a backup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupInfo {
+    pub backup_id: u32,
+    /// Creation time in seconds since the epoch.
+    pub timestamp: i64,
+    /// Total size of the backed up files in bytes.
+    pub size: u64,
+    /// Number of backed up files, some of which may be shared with other
+    /// backups.
+    pub number_files: u32,
+    pub app_metadata: Box<[u8]>,
+}
+
+impl BackupInfo {
+    fn from_pod(pod: BackupInfoPod) -> Self {
+        Self {
+            backup_id: pod.backup_id,
+            timestamp: pod.timestamp,
+            size: pod.size,
+            number_files: pod.number_files,
+            app_metadata: unsafe { as_rust_slice1(pod.app_metadata) }.into(),
+        }
+    }
+}
+
+/// Incremental backups of a database to a directory. Table files are shared
+/// between backups.
+pub struct BackupEngine {
+    inner: Pin<Box<BackupEngineWrapper>>,
+}
+
+impl BackupEngine {
+    /// Open (or create) the backups in `backup_dir`.
+    pub fn open(backup_dir: &Path) -> Result<Self> {
+        let mut inner = Box::emplace(BackupEngineWrapper::new());
+        moveit! {
+            let status = inner
+                .as_mut()
+                .open(backup_dir.as_os_str().as_bytes().into());
+        }
+        into_result(&status)?;
+        Ok(Self { inner })
+    }
+
+    /// Back up `db` and return the id of the new backup.
+    ///
+    /// If `flush_before_backup` is false, the WAL files are backed up instead
+    /// of flushing the memtables.
+    pub fn create_new_backup(
+        &mut self,
+        db: &TransactionDb,
+        flush_before_backup: bool,
+    ) -> Result<u32> {
+        self.create_new_backup_impl(db, flush_before_backup, ptr::null_mut(), ptr::null_mut())
+    }
+
+    /// Like `create_new_backup`, calling `progress` periodically while files
+    /// are copied. It is called from background threads and must not panic.
+    pub fn create_new_backup_with_progress<F: Fn() + Sync>(
+        &mut self,
+        db: &TransactionDb,
+        flush_before_backup: bool,
+        progress: F,
+    ) -> Result<u32> {
+        self.create_new_backup_impl(
+            db,
+            flush_before_backup,
+            &progress as *const F as *mut c_void,
+            progress_fn::<F> as *mut c_void,
+        )
+    }
+
+    fn create_new_backup_impl(
+        &mut self,
+        db: &TransactionDb,
+        flush_before_backup: bool,
+        ctx: *mut c_void,
+        progress_fn: *mut c_void,
+    ) -> Result<u32> {
+        let mut backup_id = 0;
+        moveit! {
+            let status = unsafe {
+                self.inner.as_mut().create_new_backup(
+                    db.as_inner(),
+                    flush_before_backup,
+                    ctx,
+                    progress_fn,
+                    &mut backup_id,
+                )
+            };
+        }
+        into_result(&status)?;
+        Ok(backup_id)
+    }
+
+    /// All backups, oldest first. Corrupt backups are not included.
+    pub fn get_backup_info(&self) -> Vec<BackupInfo> {
+        moveit! {
+            let mut infos = BackupInfosWrapper::new();
+        }
+        unsafe {
+            self.inner
+                .get_backup_info(infos.as_mut().get_unchecked_mut());
+        }
+        (0..infos.len())
+            .map(|i| BackupInfo::from_pod(infos.get(i)))
+            .collect()
+    }
+
+    /// Check that the files of a backup exist and have the expected sizes,
+    /// and also checksums if `verify_with_checksum` is true.
+    pub fn verify_backup(&self, backup_id: u32, verify_with_checksum: bool) -> Result<()> {
+        moveit! {
+            let status = self.inner.verify_backup(backup_id, verify_with_checksum);
+        }
+        into_result(&status)
+    }
+
+    /// Delete all but the latest `num_backups_to_keep` backups.
+    pub fn purge_old_backups(&mut self, num_backups_to_keep: u32) -> Result<()> {
+        moveit! {
+            let status = self.inner.as_mut().purge_old_backups(num_backups_to_keep);
+        }
+        into_result(&status)
+    }
+
+    pub fn delete_backup(&mut self, backup_id: u32) -> Result<()> {
+        moveit! {
+            let status = self.inner.as_mut().delete_backup(backup_id);
+        }
+        into_result(&status)
+    }
+
+    /// Restore a backup into `db_dir`, which must not be open. `wal_dir` is
+    /// usually the same as `db_dir`.
+    ///
+    /// If `keep_log_files` is true, existing WAL files in `wal_dir` are not
+    /// overwritten.
+    pub fn restore_db_from_backup(
+        &self,
+        backup_id: u32,
+        db_dir: &Path,
+        wal_dir: &Path,
+        keep_log_files: bool,
+    ) -> Result<()> {
+        moveit! {
+            let status = self.inner.restore_db_from_backup(
+                backup_id,
+                db_dir.as_os_str().as_bytes().into(),
+                wal_dir.as_os_str().as_bytes().into(),
+                keep_log_files,
+            );
+        }
+        into_result(&status)
+    }
+}
+
+unsafe extern "C" fn progress_fn<F: Fn() + Sync>(ctx: *mut c_void) {
+    let f = &*(ctx as *const F);
+    f();
+}
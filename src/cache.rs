@@ -0,0 +1,81 @@
+use std::{pin::Pin, sync::Arc};
+
+use autorocks_sys::{new_hyper_clock_cache, new_lru_cache, CacheWrapper};
+use moveit::Emplace;
+
+/// Block cache. Can be shared between column families and databases.
+///
+/// Clones refer to the same cache.
+#[derive(Clone)]
+pub struct Cache {
+    inner: Arc<CacheWrapper>,
+}
+
+impl Cache {
+    /// `num_shard_bits = -1` means it is automatically determined.
+    pub fn new_lru(
+        capacity: usize,
+        num_shard_bits: i32,
+        strict_capacity_limit: bool,
+        high_pri_pool_ratio: f64,
+    ) -> Self {
+        let cache = Arc::emplace(new_lru_cache(
+            capacity,
+            num_shard_bits,
+            strict_capacity_limit,
+            high_pri_pool_ratio,
+        ));
+        Self {
+            inner: Pin::into_inner(cache),
+        }
+    }
+
+    /// `estimated_entry_charge` should be about the average size of cached
+    /// blocks, e.g. the larger of `block_size` and `metadata_block_size`.
+    ///
+    /// `num_shard_bits = -1` means it is automatically determined.
+    pub fn new_hyper_clock(
+        capacity: usize,
+        estimated_entry_charge: usize,
+        num_shard_bits: i32,
+        strict_capacity_limit: bool,
+    ) -> Self {
+        let cache = Arc::emplace(new_hyper_clock_cache(
+            capacity,
+            estimated_entry_charge,
+            num_shard_bits,
+            strict_capacity_limit,
+        ));
+        Self {
+            inner: Pin::into_inner(cache),
+        }
+    }
+
+    /// Memory size of all entries in the cache.
+    pub fn usage(&self) -> usize {
+        self.inner.usage()
+    }
+
+    /// Memory size of entries that are in use by the system.
+    pub fn pinned_usage(&self) -> usize {
+        self.inner.pinned_usage()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Entries are evicted as needed if the new capacity is smaller than
+    /// the current usage.
+    pub fn set_capacity(&self, capacity: usize) {
+        self.inner.set_capacity(capacity);
+    }
+
+    pub fn set_strict_capacity_limit(&self, val: bool) {
+        self.inner.set_strict_capacity_limit(val);
+    }
+
+    pub fn as_inner(&self) -> &CacheWrapper {
+        &self.inner
+    }
+}
@@ -0,0 +1,351 @@
+use std::{pin::Pin, time::Duration};
+
+use autorocks_sys::{
+    rocksdb::{BlockBasedTableOptions_IndexType, CompactionStyle, CompressionType},
+    BlockBasedOptionsWrapper, CfOptionsWrapper,
+};
+use moveit::{moveit, Emplace};
+
+use crate::{into_result, Cache, SliceTransform};
+
+/// Column family options.
+///
+/// Apply to a column family with `DbOptions::cf_options`.
+pub struct CfOptions {
+    inner: Pin<Box<CfOptionsWrapper>>,
+}
+
+impl Default for CfOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CfOptions {
+    pub fn new() -> Self {
+        Self {
+            inner: Box::emplace(CfOptionsWrapper::new()),
+        }
+    }
+
+    /// Size of a single memtable.
+    pub fn write_buffer_size(&mut self, val: usize) -> &mut Self {
+        self.inner.as_mut().set_write_buffer_size(val);
+        self
+    }
+
+    pub fn max_write_buffer_number(&mut self, val: i32) -> &mut Self {
+        self.inner.as_mut().set_max_write_buffer_number(val);
+        self
+    }
+
+    pub fn min_write_buffer_number_to_merge(&mut self, val: i32) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_min_write_buffer_number_to_merge(val);
+        self
+    }
+
+    pub fn target_file_size_base(&mut self, val: u64) -> &mut Self {
+        self.inner.as_mut().set_target_file_size_base(val);
+        self
+    }
+
+    pub fn target_file_size_multiplier(&mut self, val: i32) -> &mut Self {
+        self.inner.as_mut().set_target_file_size_multiplier(val);
+        self
+    }
+
+    pub fn max_bytes_for_level_base(&mut self, val: u64) -> &mut Self {
+        self.inner.as_mut().set_max_bytes_for_level_base(val);
+        self
+    }
+
+    pub fn num_levels(&mut self, val: i32) -> &mut Self {
+        self.inner.as_mut().set_num_levels(val);
+        self
+    }
+
+    pub fn level0_file_num_compaction_trigger(&mut self, val: i32) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_level0_file_num_compaction_trigger(val);
+        self
+    }
+
+    pub fn level0_slowdown_writes_trigger(&mut self, val: i32) -> &mut Self {
+        self.inner.as_mut().set_level0_slowdown_writes_trigger(val);
+        self
+    }
+
+    pub fn level0_stop_writes_trigger(&mut self, val: i32) -> &mut Self {
+        self.inner.as_mut().set_level0_stop_writes_trigger(val);
+        self
+    }
+
+    pub fn compaction_style(&mut self, val: CompactionStyle) -> &mut Self {
+        self.inner.as_mut().set_compaction_style(val);
+        self
+    }
+
+    /// The corresponding feature must be enabled for this to actually work.
+    pub fn compression(&mut self, c: CompressionType) -> &mut Self {
+        self.inner.as_mut().set_compression(c);
+        self
+    }
+
+    pub fn bottommost_compression(&mut self, c: CompressionType) -> &mut Self {
+        self.inner.as_mut().set_bottommost_compression(c);
+        self
+    }
+
+    /// Compression for each level, starting from level 0. Overrides
+    /// `compression` when not empty.
+    pub fn compression_per_level(&mut self, levels: &[CompressionType]) -> &mut Self {
+        unsafe {
+            self.inner
+                .as_mut()
+                .set_compression_per_level(levels.as_ptr(), levels.len());
+        }
+        self
+    }
+
+    /// SST files with data older than `ttl` are compacted down to the last
+    /// level (or deleted under FIFO compaction). Zero disables it. Rounded
+    /// up to whole seconds.
+    pub fn ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.inner.as_mut().set_ttl(whole_secs(ttl));
+        self
+    }
+
+    /// SST files not compacted for this long are compacted again, so that
+    /// e.g. compaction filters get a chance to run. Zero disables it. Rounded
+    /// up to whole seconds.
+    pub fn periodic_compaction(&mut self, val: Duration) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_periodic_compaction_seconds(whole_secs(val));
+        self
+    }
+
+    /// Enables prefix bloom filters and prefix seek.
+    pub fn prefix_extractor(&mut self, prefix_extractor: &SliceTransform) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_prefix_extractor(prefix_extractor.as_inner());
+        self
+    }
+
+    /// Size of the memtable prefix bloom filter, as a ratio of
+    /// `write_buffer_size`. 0 (the default) disables it.
+    pub fn memtable_prefix_bloom_size_ratio(&mut self, val: f64) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_memtable_prefix_bloom_size_ratio(val);
+        self
+    }
+
+    /// Also add whole keys to the memtable bloom filter.
+    pub fn memtable_whole_key_filtering(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_memtable_whole_key_filtering(val);
+        self
+    }
+
+    pub fn block_based_table_factory(&mut self, table_options: &BlockBasedOptions) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_block_based_table_factory(&table_options.inner);
+        self
+    }
+
+    /// Store values of at least `min_blob_size` bytes in separate blob files,
+    /// so compactions don't rewrite them. Reads resolve them transparently.
+    pub fn enable_blob_files(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_enable_blob_files(val);
+        self
+    }
+
+    pub fn min_blob_size(&mut self, val: u64) -> &mut Self {
+        self.inner.as_mut().set_min_blob_size(val);
+        self
+    }
+
+    /// Blob files are closed once they reach this size.
+    pub fn blob_file_size(&mut self, val: u64) -> &mut Self {
+        self.inner.as_mut().set_blob_file_size(val);
+        self
+    }
+
+    pub fn blob_compression_type(&mut self, c: CompressionType) -> &mut Self {
+        self.inner.as_mut().set_blob_compression_type(c);
+        self
+    }
+
+    /// Relocate valid blobs out of the oldest blob files during compaction, so
+    /// the files can be deleted.
+    pub fn enable_blob_garbage_collection(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_enable_blob_garbage_collection(val);
+        self
+    }
+
+    /// Fraction of the blob files, oldest first, that garbage collection
+    /// applies to. Defaults to 0.25.
+    pub fn blob_garbage_collection_age_cutoff(&mut self, val: f64) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_blob_garbage_collection_age_cutoff(val);
+        self
+    }
+
+    /// Force compaction of the files referencing the oldest blob files when
+    /// the ratio of garbage in them reaches this. Defaults to 1.0, i.e. never.
+    pub fn blob_garbage_collection_force_threshold(&mut self, val: f64) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_blob_garbage_collection_force_threshold(val);
+        self
+    }
+
+    /// Only write blob files in flushes and compactions to this level or
+    /// below.
+    pub fn blob_file_starting_level(&mut self, val: i32) -> &mut Self {
+        self.inner.as_mut().set_blob_file_starting_level(val);
+        self
+    }
+
+    /// Cache blobs. Can be the same cache as the block cache.
+    pub fn blob_cache(&mut self, cache: &Cache) -> &mut Self {
+        self.inner.as_mut().set_blob_cache(cache.as_inner());
+        self
+    }
+
+    /// Version keys with a `u64` user-defined timestamp, for reads as of a
+    /// timestamp without holding snapshots. Can't be changed once the column
+    /// family is created.
+    ///
+    /// Writes need a timestamp: use `TransactionDb::put_with_ts` or
+    /// `Transaction::set_commit_timestamp`. Reads need one too: use
+    /// `TransactionDb::get_at` or `ReadOpts::timestamp`, otherwise they fail.
+    pub fn u64_timestamp_comparator(&mut self) -> &mut Self {
+        moveit! {
+            let status = self.inner.as_mut().set_u64_timestamp_comparator();
+        }
+        into_result(&status).expect("built-in comparator");
+        self
+    }
+
+    pub(crate) fn as_inner(&self) -> &CfOptionsWrapper {
+        &self.inner
+    }
+}
+
+/// Options for the block based table format, the default SST format.
+pub struct BlockBasedOptions {
+    inner: Pin<Box<BlockBasedOptionsWrapper>>,
+}
+
+impl Default for BlockBasedOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockBasedOptions {
+    pub fn new() -> Self {
+        Self {
+            inner: Box::emplace(BlockBasedOptionsWrapper::new()),
+        }
+    }
+
+    /// Approximate size of user data packed per block (uncompressed).
+    pub fn block_size(&mut self, val: u64) -> &mut Self {
+        self.inner.as_mut().set_block_size(val);
+        self
+    }
+
+    /// Block size for partitioned index and filter blocks.
+    pub fn metadata_block_size(&mut self, val: u64) -> &mut Self {
+        self.inner.as_mut().set_metadata_block_size(val);
+        self
+    }
+
+    pub fn format_version(&mut self, val: u32) -> &mut Self {
+        self.inner.as_mut().set_format_version(val);
+        self
+    }
+
+    /// Put index and filter blocks in the block cache instead of keeping them
+    /// in table reader memory.
+    pub fn cache_index_and_filter_blocks(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_cache_index_and_filter_blocks(val);
+        self
+    }
+
+    pub fn pin_l0_filter_and_index_blocks_in_cache(&mut self, val: bool) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_pin_l0_filter_and_index_blocks_in_cache(val);
+        self
+    }
+
+    pub fn pin_top_level_index_and_filter(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_pin_top_level_index_and_filter(val);
+        self
+    }
+
+    /// Use `kTwoLevelIndexSearch` for partitioned indexes.
+    pub fn index_type(&mut self, val: BlockBasedTableOptions_IndexType) -> &mut Self {
+        self.inner.as_mut().set_index_type(val);
+        self
+    }
+
+    /// Add whole keys to the filter (the default). Can be disabled if only
+    /// prefix filtering is needed.
+    pub fn whole_key_filtering(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_whole_key_filtering(val);
+        self
+    }
+
+    /// Partition filters like the index. Requires the `kTwoLevelIndexSearch`
+    /// index type.
+    pub fn partition_filters(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_partition_filters(val);
+        self
+    }
+
+    pub fn no_block_cache(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_no_block_cache(val);
+        self
+    }
+
+    /// Use a (possibly shared) block cache instead of the default 8MB cache
+    /// private to the table factory.
+    pub fn block_cache(&mut self, cache: &Cache) -> &mut Self {
+        self.inner.as_mut().set_block_cache(cache.as_inner());
+        self
+    }
+
+    pub fn bloom_filter(&mut self, bits_per_key: f64) -> &mut Self {
+        self.inner.as_mut().set_bloom_filter(bits_per_key);
+        self
+    }
+
+    /// Ribbon filter for levels >= `bloom_before_level`, bloom filter for the
+    /// levels before that. Use -1 to always use ribbon filters.
+    pub fn ribbon_filter(
+        &mut self,
+        bloom_equivalent_bits_per_key: f64,
+        bloom_before_level: i32,
+    ) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_ribbon_filter(bloom_equivalent_bits_per_key, bloom_before_level);
+        self
+    }
+}
+
+/// Round up, so that a non-zero duration doesn't become zero, which usually
+/// means disabled.
+pub(crate) fn whole_secs(d: Duration) -> u64 {
+    d.as_secs() + u64::from(d.subsec_nanos() > 0)
+}
@@ -1,4 +1,13 @@
-use std::{mem::MaybeUninit, os::unix::prelude::OsStrExt, path::Path, pin::Pin, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    mem::MaybeUninit,
+    os::unix::prelude::OsStrExt,
+    path::Path,
+    pin::Pin,
+    ptr,
+    sync::Arc,
+    time::Duration,
+};
 
 use autorocks_sys::{
     new_transaction_db_options, new_write_batch,
@@ -6,13 +15,19 @@ use autorocks_sys::{
         CompressionType, PinnableSlice, ReadOptions, Slice, TransactionDBOptions,
         TransactionDBWriteOptimizations, TransactionOptions, WriteOptions,
     },
-    DbOptionsWrapper, ReadOnlyDbWrapper, TransactionDBWrapper, TransactionWrapper,
+    ColumnFamilyMetaDataWrapper, DbOptionsWrapper, ExportImportFilesMetaDataWrapper,
+    LiveFilesMetaDataWrapper, PinnableWideColumnsWrapper, PropertyMapWrapper, ReadOnlyDbWrapper,
+    ReadOptionsWrapper, TablePropertiesCollectionWrapper, TransactionDBWrapper,
+    TransactionLogIteratorWrapper, TransactionWrapper,
 };
 use moveit::{moveit, Emplace, New};
 
 use crate::{
-    into_result, slice::as_rust_slice, DbIterator, Direction, Result, RocksDBStatusError, Snapshot,
-    Transaction, WriteBatch,
+    cf_options::whole_secs, event_listener, into_result, metadata::to_string, slice::as_rust_slice,
+    wide_columns, CfOptions, ColumnFamilyMetaData, DbIterator, Direction, Env, EventListener,
+    ExportImportFilesMetaData, IngestOpts, LiveFileMetaData, RateLimiter, Result,
+    RocksDBStatusError, Snapshot, SstFileManager, Statistics, TableProperties, Transaction, TtlDb,
+    WalIterator, WriteBatch, WriteBufferManager,
 };
 
 pub struct DbOptions {
@@ -47,12 +62,122 @@ impl DbOptions {
         self
     }
 
+    /// Limit total memtable memory across all column families. Ignored if a
+    /// write buffer manager is set.
+    pub fn db_write_buffer_size(&mut self, val: usize) -> &mut Self {
+        self.inner.as_mut().set_db_write_buffer_size(val);
+        self
+    }
+
+    pub fn write_buffer_manager(&mut self, manager: &WriteBufferManager) -> &mut Self {
+        self.inner
+            .as_mut()
+            .set_write_buffer_manager(manager.as_inner());
+        self
+    }
+
+    /// Use `env` for file system access and background threads, e.g.
+    /// `Env::new_mem()` for an in-memory database.
+    pub fn env(&mut self, env: &Env) -> &mut Self {
+        self.inner.as_mut().set_env(env.as_inner());
+        self
+    }
+
+    /// Limit background I/O. The limiter can be shared with other databases.
+    pub fn rate_limiter(&mut self, limiter: &RateLimiter) -> &mut Self {
+        self.inner.as_mut().set_rate_limiter(limiter.as_inner());
+        self
+    }
+
+    /// Track table files and limit their total size and deletion rate. The
+    /// manager can be shared with other databases.
+    pub fn sst_file_manager(&mut self, manager: &SstFileManager) -> &mut Self {
+        self.inner.as_mut().set_sst_file_manager(manager.as_inner());
+        self
+    }
+
+    /// Collect statistics. Disabled by default.
+    pub fn statistics(&mut self, statistics: &Statistics) -> &mut Self {
+        self.inner.as_mut().set_statistics(statistics.as_inner());
+        self
+    }
+
+    /// Listeners are called in the order they are added.
+    pub fn add_event_listener<T: EventListener>(&mut self, listener: T) -> &mut Self {
+        moveit! {
+            let listener = event_listener::new_wrapper(listener);
+        }
+        self.inner.as_mut().add_event_listener(&listener);
+        self
+    }
+
+    /// Keep WAL files that are no longer needed for recovery for this long,
+    /// e.g. for `TransactionDb::get_updates_since`. Zero (the default) means
+    /// they are deleted right away, unless `wal_size_limit_mb` is set.
+    /// Rounded up to whole seconds.
+    pub fn wal_ttl(&mut self, ttl: Duration) -> &mut Self {
+        self.inner.as_mut().set_wal_ttl_seconds(whole_secs(ttl));
+        self
+    }
+
+    /// Delete the oldest archived WAL files once their total size exceeds
+    /// this. Zero (the default) means no limit.
+    pub fn wal_size_limit_mb(&mut self, val: u64) -> &mut Self {
+        self.inner.as_mut().set_wal_size_limit_mb(val);
+        self
+    }
+
+    /// Reserve the bottommost level for files ingested with
+    /// `IngestOpts::ingest_behind`. Must be set when the database is created.
+    pub fn allow_ingest_behind(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_allow_ingest_behind(val);
+        self
+    }
+
     /// The corresponding feature must be enabled for this to actually work.
     pub fn compression(&mut self, c: CompressionType) -> &mut Self {
         self.inner.as_mut().set_compression(c);
         self
     }
 
+    /// Replace all options of column family `col`, including those set by
+    /// `compression`.
+    ///
+    /// # Panics
+    ///
+    /// If `col` is out of range. The default column family is the last one.
+    pub fn cf_options(&mut self, col: usize, options: &CfOptions) -> &mut Self {
+        assert!(col < self.inner.cf_len());
+        self.inner.as_mut().set_cf_options(col, options.as_inner());
+        self
+    }
+
+    /// Create column family `col` from exported files. The database must not
+    /// be open, and must not have column family `col` yet (e.g. it was
+    /// dropped). Call `open` afterwards.
+    ///
+    /// If `move_files` is true, the files are moved instead of copied.
+    ///
+    /// # Panics
+    ///
+    /// If `col` is out of range or is the default column family.
+    pub fn create_column_family_with_import(
+        &self,
+        col: usize,
+        metadata: &ExportImportFilesMetaData,
+        move_files: bool,
+    ) -> Result<()> {
+        assert!(col + 1 < self.inner.cf_len());
+        moveit! {
+            let mut inner_metadata = ExportImportFilesMetaDataWrapper::new();
+        }
+        metadata.to_inner(inner_metadata.as_mut());
+        moveit! {
+            let status = self.inner.import_cf(col, &inner_metadata, move_files);
+        }
+        into_result(&status)
+    }
+
     pub fn repair(&self) -> Result<()> {
         moveit! {
             let status = self.inner.repair();
@@ -64,6 +189,21 @@ impl DbOptions {
         ReadOnlyDb::open(&self.inner)
     }
 
+    /// Open a database where entries expire. `ttls[i]` is the TTL of column
+    /// family `i`. Column families without an entry (or with `None`), including
+    /// the default column family unless given, never expire. TTLs are rounded
+    /// up to whole seconds.
+    ///
+    /// Data written with `open` cannot be read correctly with this, and
+    /// vice versa.
+    ///
+    /// # Panics
+    ///
+    /// If a TTL is zero (use `None` instead) or too large.
+    pub fn open_with_ttl(&self, ttls: &[Option<Duration>]) -> Result<TtlDb> {
+        TtlDb::open(&self.inner, ttls)
+    }
+
     pub fn open(&self) -> Result<TransactionDb> {
         moveit! {
             let txn_db_options = new_transaction_db_options();
@@ -201,6 +341,132 @@ impl TransactionDb {
         Ok(Some(as_rust_slice(slice)))
     }
 
+    /// Write an entity with named wide columns. Column names must be unique.
+    ///
+    /// Transactions can't write entities in this RocksDB version.
+    pub fn put_entity<N: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &self,
+        col: usize,
+        key: &[u8],
+        columns: &[(N, V)],
+    ) -> Result<()> {
+        let mut batch = self.new_write_batch();
+        batch.put_entity(col, key, columns)?;
+        self.write(&mut batch)
+    }
+
+    pub fn put_entity_with_options<N: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &self,
+        options: &WriteOptions,
+        col: usize,
+        key: &[u8],
+        columns: &[(N, V)],
+    ) -> Result<()> {
+        moveit! {
+            let optimizations = TransactionDBWriteOptimizations::new();
+        }
+        let mut batch = self.new_write_batch();
+        batch.put_entity(col, key, columns)?;
+        self.write_with_options(options, &optimizations, &mut batch)
+    }
+
+    /// Get an entry as named wide columns, sorted by name. A plain value is
+    /// returned as a single column with an empty name.
+    pub fn get_entity(&self, col: usize, key: &[u8]) -> Result<Option<Vec<(Vec<u8>, Vec<u8>)>>> {
+        moveit! {
+            let options = ReadOptions::new();
+        }
+        self.get_entity_with_options(&options, col, key)
+    }
+
+    pub fn get_entity_with_options(
+        &self,
+        options: &ReadOptions,
+        col: usize,
+        key: &[u8],
+    ) -> Result<Option<Vec<(Vec<u8>, Vec<u8>)>>> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let mut columns = PinnableWideColumnsWrapper::new();
+            let status = unsafe {
+                self.inner.get_entity(options, cf, &key.into(), columns.as_mut().get_unchecked_mut())
+            };
+        }
+        if status.IsNotFound() {
+            return Ok(None);
+        }
+        into_result(&status)?;
+        Ok(Some(wide_columns::to_vec(&columns)))
+    }
+
+    /// Write a version of `key` at `ts`, for column families with
+    /// `CfOptions::u64_timestamp_comparator`.
+    pub fn put_with_ts(&self, col: usize, key: &[u8], ts: u64, value: &[u8]) -> Result<()> {
+        // Without a snapshot, so there is no need to set a read timestamp.
+        let mut tx = self.begin_ts_transaction();
+        tx.put(col, key, value)?;
+        tx.set_commit_timestamp(ts)?;
+        tx.commit()
+    }
+
+    /// Delete `key` as of `ts`, for column families with
+    /// `CfOptions::u64_timestamp_comparator`. Older versions stay readable.
+    pub fn delete_with_ts(&self, col: usize, key: &[u8], ts: u64) -> Result<()> {
+        let mut tx = self.begin_ts_transaction();
+        tx.delete(col, key)?;
+        tx.set_commit_timestamp(ts)?;
+        tx.commit()
+    }
+
+    fn begin_ts_transaction(&self) -> Transaction {
+        moveit! {
+            let write_options = WriteOptions::new();
+            let transaction_options = TransactionOptions::new();
+        }
+        self.begin_transaction_with_options(&write_options, &transaction_options)
+    }
+
+    /// Get the newest version of `key` at or before `ts`, for column families
+    /// with `CfOptions::u64_timestamp_comparator`.
+    pub fn get_at<'b>(
+        &self,
+        col: usize,
+        key: &[u8],
+        ts: u64,
+        buf: Pin<&'b mut PinnableSlice>,
+    ) -> Result<Option<&'b [u8]>> {
+        moveit! {
+            let mut options = ReadOptionsWrapper::new();
+        }
+        options.as_mut().set_timestamp(ts);
+        self.get_with_options((*options).as_ref(), col, key, buf)
+    }
+
+    /// Let compactions drop versions older than `ts_low` that are hidden by
+    /// newer ones. Reads as of earlier timestamps are no longer consistent.
+    /// `ts_low` can only increase.
+    pub fn increase_full_history_ts_low(&self, col: usize, ts_low: u64) -> Result<()> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let status = unsafe { self.inner.increase_full_history_ts_low(cf, ts_low) };
+        }
+        into_result(&status)
+    }
+
+    /// 0 if it was never increased.
+    pub fn full_history_ts_low(&self, col: usize) -> Result<u64> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        let mut ts_low = 0;
+        moveit! {
+            let status = unsafe { self.inner.get_full_history_ts_low(cf, &mut ts_low) };
+        }
+        into_result(&status)?;
+        Ok(ts_low)
+    }
+
     pub fn get_int_property(&self, col: usize, property: &str) -> Option<u64> {
         let cf = self.inner.get_cf(col);
         assert!(!cf.is_null());
@@ -212,6 +478,59 @@ impl TransactionDb {
         got.then_some(val)
     }
 
+    /// The error that stopped background work and writes, if any. Errors
+    /// are cleared when RocksDB recovers from them automatically or after
+    /// `resume`.
+    pub fn background_error(&self) -> Option<RocksDBStatusError> {
+        moveit! {
+            let status = self.inner.background_error();
+        }
+        into_result(&status).err()
+    }
+
+    /// Try to recover from a background error, e.g. after freeing disk
+    /// space. Does nothing if there is no error.
+    pub fn resume(&self) -> Result<()> {
+        moveit! {
+            let status = self.inner.resume();
+        }
+        into_result(&status)
+    }
+
+    /// See the `properties` module for property names.
+    pub fn get_property(&self, col: usize, property: &str) -> Option<String> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        let value = unsafe { self.inner.get_property(cf, &property.as_bytes().into()) };
+        value.as_ref().map(|v| v.to_string_lossy().into())
+    }
+
+    pub fn get_map_property(&self, col: usize, property: &str) -> Option<BTreeMap<String, String>> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let mut value = PropertyMapWrapper::new();
+        }
+        let got = unsafe {
+            self.inner.get_map_property(
+                cf,
+                &property.as_bytes().into(),
+                value.as_mut().get_unchecked_mut(),
+            )
+        };
+        got.then(|| property_map(&value))
+    }
+
+    /// Sum of an integer property over all column families.
+    pub fn get_aggregated_int_property(&self, property: &str) -> Option<u64> {
+        let mut val = 0;
+        let got = unsafe {
+            self.inner
+                .get_aggregated_int_property(&property.as_bytes().into(), &mut val)
+        };
+        got.then_some(val)
+    }
+
     pub fn snapshot(&self) -> Snapshot {
         Snapshot {
             inner: self.inner.get_snapshot(),
@@ -261,7 +580,21 @@ impl TransactionDb {
     ) -> DbIterator<&'a Self> {
         let cf = self.inner.get_cf(col);
         assert!(!cf.is_null());
-        unsafe { DbIterator::new(self.as_inner().iter(options, cf), dir) }
+        unsafe {
+            DbIterator::with_options(options, dir, |options| self.as_inner().iter(options, cf))
+        }
+    }
+
+    /// Iterate keys with the same prefix as `prefix`, as determined by the
+    /// prefix extractor of the column family, using prefix bloom filters.
+    pub fn prefix_iter(&self, col: usize, prefix: &[u8]) -> DbIterator<&'_ Self> {
+        moveit! {
+            let mut options = ReadOptionsWrapper::new();
+        }
+        options.as_mut().set_prefix_same_as_start(true);
+        let mut iter = self.iter_with_options((*options).as_ref(), col, Direction::Forward);
+        iter.seek(prefix);
+        iter
     }
 
     pub fn new_write_batch(&self) -> WriteBatch {
@@ -324,6 +657,163 @@ impl TransactionDb {
         into_result(&status)
     }
 
+    pub fn column_family_metadata(&self, col: usize) -> ColumnFamilyMetaData {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let mut metadata = ColumnFamilyMetaDataWrapper::new();
+        }
+        unsafe {
+            self.inner
+                .column_family_metadata(cf, metadata.as_mut().get_unchecked_mut());
+        }
+        ColumnFamilyMetaData::from_inner(&metadata)
+    }
+
+    /// Metadata of the SST files of all column families.
+    pub fn live_files_metadata(&self) -> Vec<LiveFileMetaData> {
+        moveit! {
+            let mut metadata = LiveFilesMetaDataWrapper::new();
+        }
+        unsafe {
+            self.inner
+                .live_files_metadata(metadata.as_mut().get_unchecked_mut());
+        }
+        LiveFileMetaData::vec_from_inner(&metadata)
+    }
+
+    /// Properties of the SST files of a column family, keyed by file path.
+    pub fn get_properties_of_all_tables(
+        &self,
+        col: usize,
+    ) -> Result<HashMap<String, TableProperties>> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let mut props = TablePropertiesCollectionWrapper::new();
+            let status = unsafe {
+                self.inner
+                    .properties_of_all_tables(cf, props.as_mut().get_unchecked_mut())
+            };
+        }
+        into_result(&status)?;
+        Ok(TableProperties::map_from_inner(&props))
+    }
+
+    /// Add SST files created by `SstFileWriter` to a column family.
+    pub fn ingest_external_files<P: AsRef<Path>>(
+        &self,
+        col: usize,
+        paths: impl IntoIterator<Item = P>,
+        options: &IngestOpts,
+    ) -> Result<()> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        let paths: Vec<_> = paths.into_iter().collect();
+        let paths: Vec<Slice> = paths
+            .iter()
+            .map(|p| p.as_ref().as_os_str().as_bytes().into())
+            .collect();
+        moveit! {
+            let status = unsafe {
+                self.inner
+                    .ingest_external_files(cf, paths.as_ptr(), paths.len(), options.as_inner())
+            };
+        }
+        into_result(&status)
+    }
+
+    /// Compact the keys in [`begin`, `end`] of a column family, `None` meaning
+    /// unbounded, and wait for the compaction to finish. Memtables holding
+    /// keys in the range are flushed first.
+    pub fn compact_range(
+        &self,
+        col: usize,
+        begin: Option<&[u8]>,
+        end: Option<&[u8]>,
+    ) -> Result<()> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        let begin: Option<Slice> = begin.map(Into::into);
+        let end: Option<Slice> = end.map(Into::into);
+        moveit! {
+            let status = unsafe {
+                self.inner.compact_range(
+                    cf,
+                    begin.as_ref().map_or(ptr::null(), |s| s as *const _),
+                    end.as_ref().map_or(ptr::null(), |s| s as *const _),
+                )
+            };
+        }
+        into_result(&status)
+    }
+
+    /// Create an openable snapshot of the database in `dir`, which must not
+    /// exist. SST files are hard linked if possible.
+    ///
+    /// Memtables are flushed first if the total size of WAL files is at least
+    /// `log_size_for_flush`. Otherwise WAL files are copied. 0 means always
+    /// flush.
+    ///
+    /// Returns the sequence number of the checkpoint.
+    pub fn create_checkpoint(&self, dir: &Path, log_size_for_flush: u64) -> Result<u64> {
+        let mut sequence_number = 0;
+        moveit! {
+            let status = unsafe {
+                self.inner.create_checkpoint(
+                    dir.as_os_str().as_bytes().into(),
+                    log_size_for_flush,
+                    &mut sequence_number,
+                )
+            };
+        }
+        into_result(&status)?;
+        Ok(sequence_number)
+    }
+
+    /// Export SST files of a column family to `dir`, which must not exist.
+    /// Files are hard linked if possible.
+    pub fn export_column_family(
+        &self,
+        col: usize,
+        dir: &Path,
+    ) -> Result<ExportImportFilesMetaData> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let mut metadata = ExportImportFilesMetaDataWrapper::new();
+            let status = unsafe {
+                self.inner.export_cf(
+                    cf,
+                    dir.as_os_str().as_bytes().into(),
+                    metadata.as_mut().get_unchecked_mut(),
+                )
+            };
+        }
+        into_result(&status)?;
+        Ok(ExportImportFilesMetaData::from_inner(&metadata))
+    }
+
+    /// Sequence number of the most recent write.
+    pub fn latest_sequence_number(&self) -> u64 {
+        self.inner.latest_sequence_number()
+    }
+
+    /// Iterate over the write batches in the WAL, starting with the batch
+    /// containing `sequence_number`. Fails if that part of the WAL has been
+    /// deleted, see `DbOptions::wal_ttl`.
+    pub fn get_updates_since(&self, sequence_number: u64) -> Result<WalIterator> {
+        let mut iter = Box::emplace(TransactionLogIteratorWrapper::new());
+        moveit! {
+            let status = unsafe {
+                self.inner
+                    .get_updates_since(sequence_number, iter.as_mut().get_unchecked_mut())
+            };
+        }
+        into_result(&status)?;
+        Ok(WalIterator::new(iter, self.clone()))
+    }
+
     pub fn as_inner(&self) -> &TransactionDBWrapper {
         &self.inner
     }
@@ -382,6 +872,36 @@ impl ReadOnlyDb {
         Ok(Some(as_rust_slice(slice)))
     }
 
+    /// Get an entry as named wide columns, sorted by name. A plain value is
+    /// returned as a single column with an empty name.
+    pub fn get_entity(&self, col: usize, key: &[u8]) -> Result<Option<Vec<(Vec<u8>, Vec<u8>)>>> {
+        moveit! {
+            let options = ReadOptions::new();
+        }
+        self.get_entity_with_options(&options, col, key)
+    }
+
+    pub fn get_entity_with_options(
+        &self,
+        options: &ReadOptions,
+        col: usize,
+        key: &[u8],
+    ) -> Result<Option<Vec<(Vec<u8>, Vec<u8>)>>> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let mut columns = PinnableWideColumnsWrapper::new();
+            let status = unsafe {
+                self.inner.get_entity(options, cf, &key.into(), columns.as_mut().get_unchecked_mut())
+            };
+        }
+        if status.IsNotFound() {
+            return Ok(None);
+        }
+        into_result(&status)?;
+        Ok(Some(wide_columns::to_vec(&columns)))
+    }
+
     pub fn iter(&self, col: usize, dir: Direction) -> DbIterator<&'_ Self> {
         moveit! {
             let options = ReadOptions::new();
@@ -397,10 +917,75 @@ impl ReadOnlyDb {
     ) -> DbIterator<&'a Self> {
         let cf = self.inner.get_cf(col);
         assert!(!cf.is_null());
-        unsafe { DbIterator::new(self.as_inner().iter(options, cf), dir) }
+        unsafe {
+            DbIterator::with_options(options, dir, |options| self.as_inner().iter(options, cf))
+        }
+    }
+
+    /// Iterate keys with the same prefix as `prefix`, as determined by the
+    /// prefix extractor of the column family, using prefix bloom filters.
+    pub fn prefix_iter(&self, col: usize, prefix: &[u8]) -> DbIterator<&'_ Self> {
+        moveit! {
+            let mut options = ReadOptionsWrapper::new();
+        }
+        options.as_mut().set_prefix_same_as_start(true);
+        let mut iter = self.iter_with_options((*options).as_ref(), col, Direction::Forward);
+        iter.seek(prefix);
+        iter
+    }
+
+    pub fn get_int_property(&self, col: usize, property: &str) -> Option<u64> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        let mut val = 0;
+        let got = unsafe {
+            self.inner
+                .get_int_property(cf, &property.as_bytes().into(), &mut val)
+        };
+        got.then_some(val)
+    }
+
+    /// See the `properties` module for property names.
+    pub fn get_property(&self, col: usize, property: &str) -> Option<String> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        let value = unsafe { self.inner.get_property(cf, &property.as_bytes().into()) };
+        value.as_ref().map(|v| v.to_string_lossy().into())
+    }
+
+    pub fn get_map_property(&self, col: usize, property: &str) -> Option<BTreeMap<String, String>> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let mut value = PropertyMapWrapper::new();
+        }
+        let got = unsafe {
+            self.inner.get_map_property(
+                cf,
+                &property.as_bytes().into(),
+                value.as_mut().get_unchecked_mut(),
+            )
+        };
+        got.then(|| property_map(&value))
+    }
+
+    /// Sum of an integer property over all column families.
+    pub fn get_aggregated_int_property(&self, property: &str) -> Option<u64> {
+        let mut val = 0;
+        let got = unsafe {
+            self.inner
+                .get_aggregated_int_property(&property.as_bytes().into(), &mut val)
+        };
+        got.then_some(val)
     }
 
     pub fn as_inner(&self) -> &ReadOnlyDbWrapper {
         &self.inner
     }
 }
+
+fn property_map(map: &PropertyMapWrapper) -> BTreeMap<String, String> {
+    (0..map.len())
+        .map(|i| unsafe { (to_string(map.key(i)), to_string(map.value(i))) })
+        .collect()
+}
@@ -0,0 +1,88 @@
+use std::{pin::Pin, sync::Arc};
+
+use autorocks_sys::{
+    default_env, new_fault_injection_fs, new_mem_env, FaultInjectionFsWrapper, SharedEnvWrapper,
+};
+use moveit::{moveit, Emplace};
+
+use crate::{into_result, Result};
+
+/// File system, clock and background threads used by databases. Set with
+/// `DbOptions::env`.
+///
+/// Clones refer to the same env.
+#[derive(Clone)]
+pub struct Env {
+    inner: Arc<SharedEnvWrapper>,
+}
+
+impl Default for Env {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Env {
+    /// The default env, using the local file system.
+    pub fn new() -> Self {
+        Self {
+            inner: Pin::into_inner(Arc::emplace(default_env())),
+        }
+    }
+
+    /// Keep files in memory instead of on disk, e.g. for tests. Files are
+    /// lost once the env and all databases using it are dropped.
+    ///
+    /// Databases still need distinct paths, but the directories don't have to
+    /// exist on disk.
+    pub fn new_mem() -> Self {
+        Self {
+            inner: Pin::into_inner(Arc::emplace(new_mem_env())),
+        }
+    }
+
+    pub fn as_inner(&self) -> &SharedEnvWrapper {
+        &self.inner
+    }
+}
+
+/// Wraps the file system of an env to test crash recovery and error
+/// handling: it can drop data that was not synced, as if the machine
+/// crashed, and fail writes on demand.
+///
+/// Use `env()` with `DbOptions::env`. Clones refer to the same file system.
+#[derive(Clone)]
+pub struct FaultInjectionFs {
+    inner: Arc<FaultInjectionFsWrapper>,
+}
+
+impl FaultInjectionFs {
+    pub fn new(base: &Env) -> Self {
+        Self {
+            inner: Pin::into_inner(Arc::emplace(new_fault_injection_fs(base.as_inner()))),
+        }
+    }
+
+    /// An env that uses this file system.
+    pub fn env(&self) -> Env {
+        Env {
+            inner: Pin::into_inner(Arc::emplace(self.inner.env())),
+        }
+    }
+
+    /// Make file creation and writes, including syncs, fail with an
+    /// `IOError` until this is called with false.
+    pub fn set_fail_writes(&self, val: bool) {
+        self.inner.set_fail_writes(val);
+    }
+
+    /// Truncate files written through this file system to their size at
+    /// their last sync. Call this after dropping the databases using it,
+    /// then reopen them to test recovery.
+    pub fn drop_unsynced_data(&self) -> Result<()> {
+        moveit! {
+            let status = self.inner.drop_unsynced_data();
+        }
+        into_result(&status)
+    }
+}
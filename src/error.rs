@@ -8,6 +8,27 @@ pub struct RocksDBStatusError {
     pub sub_code: Status_SubCode,
 }
 
+impl RocksDBStatusError {
+    /// The operation could not complete without waiting, e.g. a write with
+    /// `WriteOpts::no_slowdown` during a write stall.
+    pub fn is_incomplete(&self) -> bool {
+        self.code == Status_Code::kIncomplete
+    }
+
+    /// A deadline or I/O timeout set with `ReadOpts` was exceeded.
+    pub fn is_timed_out(&self) -> bool {
+        self.code == Status_Code::kTimedOut
+    }
+
+    /// The disk is full, or the space limit set with
+    /// `SstFileManager::set_max_allowed_space_usage` was reached.
+    pub fn is_no_space(&self) -> bool {
+        self.code == Status_Code::kIOError
+            && (self.sub_code == Status_SubCode::kNoSpace
+                || self.sub_code == Status_SubCode::kSpaceLimit)
+    }
+}
+
 impl fmt::Debug for RocksDBStatusError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RocksDBStatusError")
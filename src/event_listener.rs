@@ -0,0 +1,277 @@
+use std::{ffi::OsStr, fmt, os::unix::prelude::OsStrExt, path::PathBuf, ptr};
+
+use autocxx::c_void;
+use autorocks_sys::{
+    new_rust_event_listener,
+    rocksdb::{
+        BackgroundErrorReason, CompactionReason, FlushReason, Slice, Status,
+        TableFileCreationReason, WriteStallCondition,
+    },
+    CompactionJobInfoPod, EventListenerWrapper, FlushJobInfoPod, TableFileCreationInfoPod,
+    WriteStallInfoPod,
+};
+use moveit::New;
+
+use crate::{
+    into_result, metadata::to_string, slice::as_rust_slice1, Result, RocksDBStatusError,
+    TableProperties,
+};
+
+/// Receives database events. Register with `DbOptions::add_event_listener`.
+///
+/// Methods are called from RocksDB background threads, and should return
+/// quickly. They must not panic.
+pub trait EventListener: Send + Sync + 'static {
+    fn on_flush_completed(&self, _info: FlushJobInfo) {}
+
+    fn on_compaction_completed(&self, _info: CompactionJobInfo) {}
+
+    fn on_stall_conditions_changed(&self, _info: WriteStallInfo) {}
+
+    /// The database stops accepting writes until the error is recovered from,
+    /// automatically or with `TransactionDb::resume`.
+    fn on_background_error(&self, _reason: BackgroundErrorReason, _error: RocksDBStatusError) {}
+
+    /// Also called when creating the file failed.
+    fn on_table_file_created(&self, _info: TableFileCreationInfo) {}
+}
+
+pub struct FlushJobInfo {
+    pub cf_name: String,
+    pub file_path: PathBuf,
+    pub thread_id: u64,
+    pub job_id: i32,
+    pub triggered_writes_slowdown: bool,
+    pub triggered_writes_stop: bool,
+    pub smallest_seqno: u64,
+    pub largest_seqno: u64,
+    pub flush_reason: FlushReason,
+    pub table_properties: TableProperties,
+}
+
+pub struct CompactionJobInfo {
+    pub cf_name: String,
+    pub status: Result<()>,
+    pub thread_id: u64,
+    pub job_id: i32,
+    pub base_input_level: i32,
+    pub output_level: i32,
+    pub input_files: Vec<PathBuf>,
+    pub output_files: Vec<PathBuf>,
+    pub compaction_reason: CompactionReason,
+    pub elapsed_micros: u64,
+    pub num_input_records: u64,
+    pub num_output_records: u64,
+    pub total_input_bytes: u64,
+    pub total_output_bytes: u64,
+}
+
+pub struct WriteStallInfo {
+    pub cf_name: String,
+    pub cur: WriteStallCondition,
+    pub prev: WriteStallCondition,
+}
+
+pub struct TableFileCreationInfo {
+    pub db_name: String,
+    pub cf_name: String,
+    pub file_path: PathBuf,
+    pub job_id: i32,
+    pub reason: TableFileCreationReason,
+    pub status: Result<()>,
+    /// File size in bytes.
+    pub file_size: u64,
+    pub table_properties: TableProperties,
+}
+
+// The autocxx enums don't implement Debug, so they are formatted as integers.
+
+impl fmt::Debug for FlushJobInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlushJobInfo")
+            .field("cf_name", &self.cf_name)
+            .field("file_path", &self.file_path)
+            .field("thread_id", &self.thread_id)
+            .field("job_id", &self.job_id)
+            .field("triggered_writes_slowdown", &self.triggered_writes_slowdown)
+            .field("triggered_writes_stop", &self.triggered_writes_stop)
+            .field("smallest_seqno", &self.smallest_seqno)
+            .field("largest_seqno", &self.largest_seqno)
+            .field("flush_reason", &(self.flush_reason.clone() as i32))
+            .field("table_properties", &self.table_properties)
+            .finish()
+    }
+}
+
+impl fmt::Debug for CompactionJobInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompactionJobInfo")
+            .field("cf_name", &self.cf_name)
+            .field("status", &self.status)
+            .field("thread_id", &self.thread_id)
+            .field("job_id", &self.job_id)
+            .field("base_input_level", &self.base_input_level)
+            .field("output_level", &self.output_level)
+            .field("input_files", &self.input_files)
+            .field("output_files", &self.output_files)
+            .field(
+                "compaction_reason",
+                &(self.compaction_reason.clone() as i32),
+            )
+            .field("elapsed_micros", &self.elapsed_micros)
+            .field("num_input_records", &self.num_input_records)
+            .field("num_output_records", &self.num_output_records)
+            .field("total_input_bytes", &self.total_input_bytes)
+            .field("total_output_bytes", &self.total_output_bytes)
+            .finish()
+    }
+}
+
+impl fmt::Debug for WriteStallInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteStallInfo")
+            .field("cf_name", &self.cf_name)
+            .field("cur", &(self.cur.clone() as i32))
+            .field("prev", &(self.prev.clone() as i32))
+            .finish()
+    }
+}
+
+impl fmt::Debug for TableFileCreationInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TableFileCreationInfo")
+            .field("db_name", &self.db_name)
+            .field("cf_name", &self.cf_name)
+            .field("file_path", &self.file_path)
+            .field("job_id", &self.job_id)
+            .field("reason", &(self.reason.clone() as i32))
+            .field("status", &self.status)
+            .field("file_size", &self.file_size)
+            .field("table_properties", &self.table_properties)
+            .finish()
+    }
+}
+
+pub(crate) fn new_wrapper<T: EventListener>(
+    listener: T,
+) -> impl New<Output = EventListenerWrapper> {
+    let ctx = Box::into_raw(Box::new(listener));
+    unsafe {
+        new_rust_event_listener(
+            ctx as *mut c_void,
+            on_flush_completed_fn::<T> as *mut c_void,
+            on_compaction_completed_fn::<T> as *mut c_void,
+            on_stall_conditions_changed_fn::<T> as *mut c_void,
+            on_background_error_fn::<T> as *mut c_void,
+            on_table_file_created_fn::<T> as *mut c_void,
+            drop_fn::<T> as *mut c_void,
+        )
+    }
+}
+
+// The pods are trivially copyable, so they are read out of the pointers and
+// their borrowed fields copied before the callbacks return.
+
+unsafe extern "C" fn on_flush_completed_fn<T: EventListener>(
+    ctx: *mut c_void,
+    info: *const FlushJobInfoPod,
+) {
+    let t = &*(ctx as *const T);
+    let info = ptr::read(info);
+    t.on_flush_completed(FlushJobInfo {
+        cf_name: to_string(info.cf_name),
+        file_path: to_path_buf(info.file_path),
+        thread_id: info.thread_id,
+        job_id: info.job_id,
+        triggered_writes_slowdown: info.triggered_writes_slowdown,
+        triggered_writes_stop: info.triggered_writes_stop,
+        smallest_seqno: info.smallest_seqno,
+        largest_seqno: info.largest_seqno,
+        flush_reason: info.flush_reason,
+        table_properties: TableProperties::from_pod(info.table_properties),
+    });
+}
+
+unsafe extern "C" fn on_compaction_completed_fn<T: EventListener>(
+    ctx: *mut c_void,
+    info: *const CompactionJobInfoPod,
+    status: *const Status,
+) {
+    let t = &*(ctx as *const T);
+    let info = ptr::read(info);
+    t.on_compaction_completed(CompactionJobInfo {
+        cf_name: to_string(info.cf_name),
+        status: into_result(&*status),
+        thread_id: info.thread_id,
+        job_id: info.job_id,
+        base_input_level: info.base_input_level,
+        output_level: info.output_level,
+        input_files: to_path_bufs(info.input_files, info.input_files_len),
+        output_files: to_path_bufs(info.output_files, info.output_files_len),
+        compaction_reason: info.compaction_reason,
+        elapsed_micros: info.elapsed_micros,
+        num_input_records: info.num_input_records,
+        num_output_records: info.num_output_records,
+        total_input_bytes: info.total_input_bytes,
+        total_output_bytes: info.total_output_bytes,
+    });
+}
+
+unsafe extern "C" fn on_stall_conditions_changed_fn<T: EventListener>(
+    ctx: *mut c_void,
+    info: *const WriteStallInfoPod,
+) {
+    let t = &*(ctx as *const T);
+    let info = ptr::read(info);
+    t.on_stall_conditions_changed(WriteStallInfo {
+        cf_name: to_string(info.cf_name),
+        cur: info.cur,
+        prev: info.prev,
+    });
+}
+
+unsafe extern "C" fn on_background_error_fn<T: EventListener>(
+    ctx: *mut c_void,
+    reason: *const BackgroundErrorReason,
+    error: *const Status,
+) {
+    let t = &*(ctx as *const T);
+    if let Err(e) = into_result(&*error) {
+        t.on_background_error((*reason).clone(), e);
+    }
+}
+
+unsafe extern "C" fn on_table_file_created_fn<T: EventListener>(
+    ctx: *mut c_void,
+    info: *const TableFileCreationInfoPod,
+    status: *const Status,
+) {
+    let t = &*(ctx as *const T);
+    let info = ptr::read(info);
+    t.on_table_file_created(TableFileCreationInfo {
+        db_name: to_string(info.db_name),
+        cf_name: to_string(info.cf_name),
+        file_path: to_path_buf(info.file_path),
+        job_id: info.job_id,
+        reason: info.reason,
+        status: into_result(&*status),
+        file_size: info.file_size,
+        table_properties: TableProperties::from_pod(info.table_properties),
+    });
+}
+
+unsafe extern "C" fn drop_fn<T: EventListener>(ctx: *mut c_void) {
+    drop(Box::from_raw(ctx as *mut T));
+}
+
+/// Safety: s must be valid.
+unsafe fn to_path_buf(s: Slice) -> PathBuf {
+    OsStr::from_bytes(as_rust_slice1(s)).into()
+}
+
+/// Safety: files must point to len valid slices.
+unsafe fn to_path_bufs(files: *const Slice, len: usize) -> Vec<PathBuf> {
+    (0..len)
+        .map(|i| to_path_buf(ptr::read(files.add(i))))
+        .collect()
+}
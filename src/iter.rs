@@ -1,9 +1,17 @@
 use std::{hint::unreachable_unchecked, marker::PhantomData, pin::Pin};
 
 use autocxx::prelude::UniquePtr;
-use autorocks_sys::rocksdb::Iterator;
+use autorocks_sys::{
+    iterator_column, iterator_columns_len,
+    rocksdb::{Iterator, ReadOptions},
+    ReadOptionsWrapper,
+};
+use moveit::{moveit, Emplace};
 
-use crate::slice::as_rust_slice1;
+use crate::{
+    into_result, slice::as_rust_slice1, wide_columns, ReadOnlyDb, Result, SharedSnapshot, Snapshot,
+    TransactionDb,
+};
 
 pub enum Direction {
     Forward,
@@ -12,6 +20,8 @@ pub enum Direction {
 
 pub struct DbIterator<T> {
     pub(crate) inner: UniquePtr<Iterator>,
+    // Declared after inner so that it is dropped after it.
+    pub(crate) options: Option<Pin<Box<ReadOptionsWrapper>>>,
     pub(crate) just_seeked: bool,
     pub(crate) direction: Direction,
     pub(crate) phantom: PhantomData<T>,
@@ -27,12 +37,32 @@ impl<T> DbIterator<T> {
         }
         Self {
             inner,
+            options: None,
             just_seeked: true,
             direction,
             phantom: PhantomData,
         }
     }
 
+    /// Create an iterator with `iter` from a copy of `options` that the
+    /// iterator keeps alive, since RocksDB iterators keep pointers into their
+    /// options, e.g. to timestamps.
+    ///
+    /// Safety: `iter` must NOT return null.
+    pub(crate) unsafe fn with_options(
+        options: &ReadOptions,
+        direction: Direction,
+        iter: impl FnOnce(&ReadOptions) -> UniquePtr<Iterator>,
+    ) -> Self {
+        let mut copy = Box::emplace(ReadOptionsWrapper::new());
+        copy.as_mut().copy_from(options);
+        let inner = iter((*copy).as_ref());
+        Self {
+            options: Some(copy),
+            ..Self::new(inner, direction)
+        }
+    }
+
     pub fn as_inner(&self) -> &Iterator {
         unsafe { unwrap_unchecked(self.inner.as_ref()) }
     }
@@ -63,6 +93,15 @@ impl<T> DbIterator<T> {
         }
     }
 
+    /// Check whether iteration stopped because of an error, e.g. a deadline
+    /// set with `ReadOpts::deadline`, rather than the end of the data.
+    pub fn status(&self) -> Result<()> {
+        moveit! {
+            let status = self.as_inner().status();
+        }
+        into_result(&status)
+    }
+
     pub fn value(&self) -> Option<&[u8]> {
         if self.valid() {
             Some(unsafe { as_rust_slice1(self.as_inner().value()) })
@@ -70,6 +109,62 @@ impl<T> DbIterator<T> {
             None
         }
     }
+
+    /// Safety: the iterator must support wide columns. Transaction, TTL and
+    /// SST file iterators don't in this RocksDB version.
+    unsafe fn columns_unchecked(&self) -> Option<Vec<(&[u8], &[u8])>> {
+        if self.valid() {
+            let iter = self.as_inner();
+            Some(
+                (0..iterator_columns_len(iter))
+                    .map(|i| wide_columns::from_pod(iterator_column(iter, i)))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl DbIterator<&TransactionDb> {
+    /// Named wide columns of the current entry, sorted by name. A plain value
+    /// is returned as a single column with an empty name.
+    pub fn columns(&self) -> Option<Vec<(&[u8], &[u8])>> {
+        unsafe { self.columns_unchecked() }
+    }
+
+    /// Timestamp of the current entry. The column family must use a
+    /// timestamp comparator, e.g. `CfOptions::u64_timestamp_comparator`.
+    /// `None` if the timestamp isn't a u64, i.e. not 8 bytes.
+    pub fn timestamp(&self) -> Option<u64> {
+        if self.valid() {
+            let ts = unsafe { as_rust_slice1(self.as_inner().timestamp()) };
+            ts.try_into().ok().map(u64::from_le_bytes)
+        } else {
+            None
+        }
+    }
+}
+
+impl DbIterator<&ReadOnlyDb> {
+    /// See `DbIterator<&TransactionDb>::columns`.
+    pub fn columns(&self) -> Option<Vec<(&[u8], &[u8])>> {
+        unsafe { self.columns_unchecked() }
+    }
+}
+
+impl DbIterator<&Snapshot> {
+    /// See `DbIterator<&TransactionDb>::columns`.
+    pub fn columns(&self) -> Option<Vec<(&[u8], &[u8])>> {
+        unsafe { self.columns_unchecked() }
+    }
+}
+
+impl DbIterator<&SharedSnapshot> {
+    /// See `DbIterator<&TransactionDb>::columns`.
+    pub fn columns(&self) -> Option<Vec<(&[u8], &[u8])>> {
+        unsafe { self.columns_unchecked() }
+    }
 }
 
 impl<T> core::iter::Iterator for DbIterator<T> {
@@ -1,17 +1,57 @@
 pub extern crate autorocks_sys;
 pub extern crate moveit;
 
+#[cfg(feature = "async")]
+mod async_db;
+mod backup;
+mod cache;
+mod cf_options;
 mod db;
+mod env;
 mod error;
+mod event_listener;
 mod iter;
+mod metadata;
+mod perf_context;
+pub mod properties;
+mod rate_limiter;
+mod read_opts;
 mod slice;
+mod slice_transform;
 mod snapshot;
+mod sst_file;
+mod sst_file_manager;
+mod statistics;
 mod transaction;
+mod ttl_db;
+mod wal;
+mod wide_columns;
 mod write_batch;
+mod write_buffer_manager;
+mod write_opts;
 
+#[cfg(feature = "async")]
+pub use async_db::*;
+pub use backup::*;
+pub use cache::*;
+pub use cf_options::*;
 pub use db::*;
+pub use env::*;
 pub use error::*;
+pub use event_listener::*;
 pub use iter::*;
+pub use metadata::*;
+pub use perf_context::*;
+pub use rate_limiter::*;
+pub use read_opts::*;
+pub use slice_transform::*;
 pub use snapshot::*;
+pub use sst_file::*;
+pub use sst_file_manager::*;
+pub use statistics::*;
 pub use transaction::*;
+pub use ttl_db::*;
+pub use wal::*;
 pub use write_batch::*;
+pub use write_buffer_manager::*;
+pub use write_opts::*;
@@ -0,0 +1,214 @@
+use std::{collections::HashMap, pin::Pin};
+
+use autorocks_sys::{
+    rocksdb::Slice, ColumnFamilyMetaDataWrapper, ExportImportFilesMetaDataWrapper,
+    LiveFileMetaDataPod, LiveFilesMetaDataWrapper, TablePropertiesCollectionWrapper,
+    TablePropertiesPod,
+};
+
+use crate::slice::as_rust_slice1;
+
+/// Metadata of an SST file in a database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveFileMetaData {
+    pub column_family_name: String,
+    pub level: i32,
+    /// File name relative to `directory`.
+    pub relative_filename: String,
+    pub directory: String,
+    /// File size in bytes.
+    pub size: u64,
+    pub smallest_seqno: u64,
+    pub largest_seqno: u64,
+    pub smallest_key: Box<[u8]>,
+    pub largest_key: Box<[u8]>,
+    pub num_entries: u64,
+    pub num_deletions: u64,
+}
+
+impl LiveFileMetaData {
+    pub(crate) fn from_pod(pod: LiveFileMetaDataPod) -> Self {
+        unsafe {
+            Self {
+                column_family_name: to_string(pod.column_family_name),
+                level: pod.level,
+                relative_filename: to_string(pod.relative_filename),
+                directory: to_string(pod.directory),
+                size: pod.size,
+                smallest_seqno: pod.smallest_seqno,
+                largest_seqno: pod.largest_seqno,
+                smallest_key: as_rust_slice1(pod.smallest_key).into(),
+                largest_key: as_rust_slice1(pod.largest_key).into(),
+                num_entries: pod.num_entries,
+                num_deletions: pod.num_deletions,
+            }
+        }
+    }
+
+    pub(crate) fn vec_from_inner(inner: &LiveFilesMetaDataWrapper) -> Vec<Self> {
+        (0..inner.len())
+            .map(|i| Self::from_pod(inner.get(i)))
+            .collect()
+    }
+
+    /// The returned pod borrows from self.
+    pub(crate) fn as_pod(&self) -> LiveFileMetaDataPod {
+        LiveFileMetaDataPod {
+            column_family_name: self.column_family_name.as_bytes().into(),
+            level: self.level,
+            relative_filename: self.relative_filename.as_bytes().into(),
+            directory: self.directory.as_bytes().into(),
+            size: self.size,
+            smallest_seqno: self.smallest_seqno,
+            largest_seqno: self.largest_seqno,
+            smallest_key: (&*self.smallest_key).into(),
+            largest_key: (&*self.largest_key).into(),
+            num_entries: self.num_entries,
+            num_deletions: self.num_deletions,
+        }
+    }
+}
+
+/// Files of a column family, by level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnFamilyMetaData {
+    pub name: String,
+    /// Total size of the files in bytes.
+    pub size: u64,
+    pub levels: Vec<LevelMetaData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelMetaData {
+    pub level: i32,
+    /// Total size of the files in bytes.
+    pub size: u64,
+    pub files: Vec<LiveFileMetaData>,
+}
+
+impl ColumnFamilyMetaData {
+    pub(crate) fn from_inner(inner: &ColumnFamilyMetaDataWrapper) -> Self {
+        Self {
+            name: unsafe { to_string(inner.name()) },
+            size: inner.size(),
+            levels: (0..inner.level_len())
+                .map(|l| LevelMetaData {
+                    level: inner.level(l),
+                    size: inner.level_size(l),
+                    files: (0..inner.file_len(l))
+                        .map(|i| LiveFileMetaData::from_pod(inner.file(l, i)))
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.levels.iter().map(|l| l.files.len()).sum()
+    }
+}
+
+/// Files of an exported column family. See
+/// `TransactionDb::export_column_family` and
+/// `DbOptions::create_column_family_with_import`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportImportFilesMetaData {
+    /// Used to check that the importing column family uses the same
+    /// comparator.
+    pub db_comparator_name: String,
+    pub files: Vec<LiveFileMetaData>,
+}
+
+impl ExportImportFilesMetaData {
+    pub(crate) fn from_inner(inner: &ExportImportFilesMetaDataWrapper) -> Self {
+        Self {
+            db_comparator_name: unsafe { to_string(inner.db_comparator_name()) },
+            files: (0..inner.len())
+                .map(|i| LiveFileMetaData::from_pod(inner.get(i)))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn to_inner(&self, mut inner: Pin<&mut ExportImportFilesMetaDataWrapper>) {
+        inner
+            .as_mut()
+            .set_db_comparator_name(self.db_comparator_name.as_bytes().into());
+        for f in &self.files {
+            inner.as_mut().push(&f.as_pod());
+        }
+    }
+}
+
+/// Properties of an SST file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableProperties {
+    /// Total size of data blocks.
+    pub data_size: u64,
+    pub index_size: u64,
+    pub filter_size: u64,
+    /// Total size of keys, uncompressed.
+    pub raw_key_size: u64,
+    /// Total size of values, uncompressed.
+    pub raw_value_size: u64,
+    pub num_data_blocks: u64,
+    pub num_entries: u64,
+    pub num_deletions: u64,
+    pub num_merge_operands: u64,
+    pub num_range_deletions: u64,
+    pub format_version: u64,
+    /// Time the oldest data in the file was written, in seconds since the
+    /// epoch. 0 if unknown.
+    pub creation_time: u64,
+    pub oldest_key_time: u64,
+    /// Time the file was created, in seconds since the epoch. 0 if unknown.
+    pub file_creation_time: u64,
+    pub column_family_name: String,
+    pub comparator_name: String,
+    pub merge_operator_name: String,
+    pub prefix_extractor_name: String,
+    pub filter_policy_name: String,
+    pub compression_name: String,
+}
+
+impl TableProperties {
+    pub(crate) fn from_pod(pod: TablePropertiesPod) -> Self {
+        unsafe {
+            Self {
+                data_size: pod.data_size,
+                index_size: pod.index_size,
+                filter_size: pod.filter_size,
+                raw_key_size: pod.raw_key_size,
+                raw_value_size: pod.raw_value_size,
+                num_data_blocks: pod.num_data_blocks,
+                num_entries: pod.num_entries,
+                num_deletions: pod.num_deletions,
+                num_merge_operands: pod.num_merge_operands,
+                num_range_deletions: pod.num_range_deletions,
+                format_version: pod.format_version,
+                creation_time: pod.creation_time,
+                oldest_key_time: pod.oldest_key_time,
+                file_creation_time: pod.file_creation_time,
+                column_family_name: to_string(pod.column_family_name),
+                comparator_name: to_string(pod.comparator_name),
+                merge_operator_name: to_string(pod.merge_operator_name),
+                prefix_extractor_name: to_string(pod.prefix_extractor_name),
+                filter_policy_name: to_string(pod.filter_policy_name),
+                compression_name: to_string(pod.compression_name),
+            }
+        }
+    }
+
+    /// Keyed by file path.
+    pub(crate) fn map_from_inner(
+        inner: &TablePropertiesCollectionWrapper,
+    ) -> HashMap<String, Self> {
+        (0..inner.len())
+            .map(|i| unsafe { (to_string(inner.file_name(i)), Self::from_pod(inner.get(i))) })
+            .collect()
+    }
+}
+
+/// Safety: s must be valid.
+pub(crate) unsafe fn to_string(s: Slice) -> String {
+    String::from_utf8_lossy(as_rust_slice1(s)).into_owned()
+}
@@ -0,0 +1,235 @@
+use std::{cell::Cell, marker::PhantomData};
+
+use autorocks_sys::{
+    get_perf_level, iostats_context_snapshot, perf_context_snapshot, reset_iostats_context,
+    reset_perf_context, rocksdb::PerfLevel, set_perf_level, IOStatsContextPod, PerfContextPod,
+};
+
+/// Per-thread performance counters. Times are in nanoseconds.
+///
+/// Enable with `PerfContext::enable`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PerfContext {
+    pub user_key_comparison_count: u64,
+    pub block_cache_hit_count: u64,
+    pub block_read_count: u64,
+    pub block_read_byte: u64,
+    pub block_read_time: u64,
+    pub block_cache_index_hit_count: u64,
+    pub index_block_read_count: u64,
+    pub block_cache_filter_hit_count: u64,
+    pub filter_block_read_count: u64,
+    pub block_checksum_time: u64,
+    pub block_decompress_time: u64,
+    pub get_read_bytes: u64,
+    pub multiget_read_bytes: u64,
+    pub iter_read_bytes: u64,
+    pub internal_key_skipped_count: u64,
+    pub internal_delete_skipped_count: u64,
+    pub internal_recent_skipped_count: u64,
+    pub internal_merge_count: u64,
+    pub get_snapshot_time: u64,
+    pub get_from_memtable_time: u64,
+    pub get_from_memtable_count: u64,
+    pub get_post_process_time: u64,
+    pub get_from_output_files_time: u64,
+    pub seek_on_memtable_time: u64,
+    pub seek_on_memtable_count: u64,
+    pub next_on_memtable_count: u64,
+    pub prev_on_memtable_count: u64,
+    pub seek_child_seek_time: u64,
+    pub seek_child_seek_count: u64,
+    pub seek_min_heap_time: u64,
+    pub seek_max_heap_time: u64,
+    pub seek_internal_seek_time: u64,
+    pub find_next_user_entry_time: u64,
+    pub write_wal_time: u64,
+    pub write_memtable_time: u64,
+    pub write_delay_time: u64,
+    pub write_scheduling_flushes_compactions_time: u64,
+    pub write_pre_and_post_process_time: u64,
+    pub write_thread_wait_nanos: u64,
+    pub db_mutex_lock_nanos: u64,
+    pub db_condition_wait_nanos: u64,
+    pub read_index_block_nanos: u64,
+    pub read_filter_block_nanos: u64,
+    pub new_table_block_iter_nanos: u64,
+    pub new_table_iterator_nanos: u64,
+    pub block_seek_nanos: u64,
+    pub find_table_nanos: u64,
+    pub bloom_memtable_hit_count: u64,
+    pub bloom_memtable_miss_count: u64,
+    pub bloom_sst_hit_count: u64,
+    pub bloom_sst_miss_count: u64,
+    pub key_lock_wait_time: u64,
+    pub key_lock_wait_count: u64,
+    pub get_cpu_nanos: u64,
+    pub iter_next_cpu_nanos: u64,
+    pub iter_prev_cpu_nanos: u64,
+    pub iter_seek_cpu_nanos: u64,
+}
+
+/// Per-thread IO counters. Times are in nanoseconds.
+///
+/// Enabled together with `PerfContext::enable`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IOStatsContext {
+    pub bytes_written: u64,
+    pub bytes_read: u64,
+    pub open_nanos: u64,
+    pub allocate_nanos: u64,
+    pub write_nanos: u64,
+    pub read_nanos: u64,
+    pub range_sync_nanos: u64,
+    pub fsync_nanos: u64,
+    pub prepare_write_nanos: u64,
+    pub logger_nanos: u64,
+    pub cpu_write_nanos: u64,
+    pub cpu_read_nanos: u64,
+}
+
+impl PerfContext {
+    fn from_pod(pod: PerfContextPod) -> Self {
+        Self {
+            user_key_comparison_count: pod.user_key_comparison_count,
+            block_cache_hit_count: pod.block_cache_hit_count,
+            block_read_count: pod.block_read_count,
+            block_read_byte: pod.block_read_byte,
+            block_read_time: pod.block_read_time,
+            block_cache_index_hit_count: pod.block_cache_index_hit_count,
+            index_block_read_count: pod.index_block_read_count,
+            block_cache_filter_hit_count: pod.block_cache_filter_hit_count,
+            filter_block_read_count: pod.filter_block_read_count,
+            block_checksum_time: pod.block_checksum_time,
+            block_decompress_time: pod.block_decompress_time,
+            get_read_bytes: pod.get_read_bytes,
+            multiget_read_bytes: pod.multiget_read_bytes,
+            iter_read_bytes: pod.iter_read_bytes,
+            internal_key_skipped_count: pod.internal_key_skipped_count,
+            internal_delete_skipped_count: pod.internal_delete_skipped_count,
+            internal_recent_skipped_count: pod.internal_recent_skipped_count,
+            internal_merge_count: pod.internal_merge_count,
+            get_snapshot_time: pod.get_snapshot_time,
+            get_from_memtable_time: pod.get_from_memtable_time,
+            get_from_memtable_count: pod.get_from_memtable_count,
+            get_post_process_time: pod.get_post_process_time,
+            get_from_output_files_time: pod.get_from_output_files_time,
+            seek_on_memtable_time: pod.seek_on_memtable_time,
+            seek_on_memtable_count: pod.seek_on_memtable_count,
+            next_on_memtable_count: pod.next_on_memtable_count,
+            prev_on_memtable_count: pod.prev_on_memtable_count,
+            seek_child_seek_time: pod.seek_child_seek_time,
+            seek_child_seek_count: pod.seek_child_seek_count,
+            seek_min_heap_time: pod.seek_min_heap_time,
+            seek_max_heap_time: pod.seek_max_heap_time,
+            seek_internal_seek_time: pod.seek_internal_seek_time,
+            find_next_user_entry_time: pod.find_next_user_entry_time,
+            write_wal_time: pod.write_wal_time,
+            write_memtable_time: pod.write_memtable_time,
+            write_delay_time: pod.write_delay_time,
+            write_scheduling_flushes_compactions_time: pod
+                .write_scheduling_flushes_compactions_time,
+            write_pre_and_post_process_time: pod.write_pre_and_post_process_time,
+            write_thread_wait_nanos: pod.write_thread_wait_nanos,
+            db_mutex_lock_nanos: pod.db_mutex_lock_nanos,
+            db_condition_wait_nanos: pod.db_condition_wait_nanos,
+            read_index_block_nanos: pod.read_index_block_nanos,
+            read_filter_block_nanos: pod.read_filter_block_nanos,
+            new_table_block_iter_nanos: pod.new_table_block_iter_nanos,
+            new_table_iterator_nanos: pod.new_table_iterator_nanos,
+            block_seek_nanos: pod.block_seek_nanos,
+            find_table_nanos: pod.find_table_nanos,
+            bloom_memtable_hit_count: pod.bloom_memtable_hit_count,
+            bloom_memtable_miss_count: pod.bloom_memtable_miss_count,
+            bloom_sst_hit_count: pod.bloom_sst_hit_count,
+            bloom_sst_miss_count: pod.bloom_sst_miss_count,
+            key_lock_wait_time: pod.key_lock_wait_time,
+            key_lock_wait_count: pod.key_lock_wait_count,
+            get_cpu_nanos: pod.get_cpu_nanos,
+            iter_next_cpu_nanos: pod.iter_next_cpu_nanos,
+            iter_prev_cpu_nanos: pod.iter_prev_cpu_nanos,
+            iter_seek_cpu_nanos: pod.iter_seek_cpu_nanos,
+        }
+    }
+}
+
+impl IOStatsContext {
+    fn from_pod(pod: IOStatsContextPod) -> Self {
+        Self {
+            bytes_written: pod.bytes_written,
+            bytes_read: pod.bytes_read,
+            open_nanos: pod.open_nanos,
+            allocate_nanos: pod.allocate_nanos,
+            write_nanos: pod.write_nanos,
+            read_nanos: pod.read_nanos,
+            range_sync_nanos: pod.range_sync_nanos,
+            fsync_nanos: pod.fsync_nanos,
+            prepare_write_nanos: pod.prepare_write_nanos,
+            logger_nanos: pod.logger_nanos,
+            cpu_write_nanos: pod.cpu_write_nanos,
+            cpu_read_nanos: pod.cpu_read_nanos,
+        }
+    }
+}
+
+thread_local! {
+    // Whether a `PerfContextGuard` is alive on this thread.
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+}
+
+impl PerfContext {
+    /// Enable perf and IO stats counters for the current thread, starting
+    /// from zero. The previous level is restored when the guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// If a guard is already alive on this thread. Resetting the counters
+    /// would corrupt its measurement.
+    pub fn enable(level: PerfLevel) -> PerfContextGuard {
+        assert!(
+            !ENABLED.with(|enabled| enabled.replace(true)),
+            "perf context is already enabled on this thread"
+        );
+        let prev_level = get_perf_level();
+        set_perf_level(level);
+        reset_perf_context();
+        reset_iostats_context();
+        PerfContextGuard {
+            prev_level,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+/// Keeps perf counters enabled for the current thread. Dropping it resets
+/// the counters, so that they don't leak into the next measurement on this
+/// thread.
+pub struct PerfContextGuard {
+    prev_level: PerfLevel,
+    // The counters are thread local.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl PerfContextGuard {
+    /// Counters of the current thread since it was enabled or reset.
+    pub fn perf_context(&self) -> PerfContext {
+        PerfContext::from_pod(perf_context_snapshot())
+    }
+
+    pub fn iostats_context(&self) -> IOStatsContext {
+        IOStatsContext::from_pod(iostats_context_snapshot())
+    }
+
+    pub fn reset(&self) {
+        reset_perf_context();
+        reset_iostats_context();
+    }
+}
+
+impl Drop for PerfContextGuard {
+    fn drop(&mut self) {
+        self.reset();
+        set_perf_level(self.prev_level.clone());
+        ENABLED.with(|enabled| enabled.set(false));
+    }
+}
@@ -0,0 +1,67 @@
+//! Names of well-known properties, for `get_property`, `get_int_property`
+//! and `get_map_property`.
+
+/// Append the level number, e.g. `format!("{NUM_FILES_AT_LEVEL_PREFIX}0")`.
+pub const NUM_FILES_AT_LEVEL_PREFIX: &str = "rocksdb.num-files-at-level";
+/// Append the level number.
+pub const COMPRESSION_RATIO_AT_LEVEL_PREFIX: &str = "rocksdb.compression-ratio-at-level";
+pub const STATS: &str = "rocksdb.stats";
+pub const SSTABLES: &str = "rocksdb.sstables";
+/// String or map property.
+pub const CF_STATS: &str = "rocksdb.cfstats";
+pub const CF_STATS_NO_FILE_HISTOGRAM: &str = "rocksdb.cfstats-no-file-histogram";
+pub const CF_FILE_HISTOGRAM: &str = "rocksdb.cf-file-histogram";
+/// String or map property.
+pub const DB_STATS: &str = "rocksdb.dbstats";
+pub const LEVEL_STATS: &str = "rocksdb.levelstats";
+/// String or map property.
+pub const BLOCK_CACHE_ENTRY_STATS: &str = "rocksdb.block-cache-entry-stats";
+pub const NUM_IMMUTABLE_MEM_TABLE: &str = "rocksdb.num-immutable-mem-table";
+pub const NUM_IMMUTABLE_MEM_TABLE_FLUSHED: &str = "rocksdb.num-immutable-mem-table-flushed";
+pub const MEM_TABLE_FLUSH_PENDING: &str = "rocksdb.mem-table-flush-pending";
+pub const NUM_RUNNING_FLUSHES: &str = "rocksdb.num-running-flushes";
+pub const COMPACTION_PENDING: &str = "rocksdb.compaction-pending";
+pub const NUM_RUNNING_COMPACTIONS: &str = "rocksdb.num-running-compactions";
+pub const BACKGROUND_ERRORS: &str = "rocksdb.background-errors";
+pub const CUR_SIZE_ACTIVE_MEM_TABLE: &str = "rocksdb.cur-size-active-mem-table";
+pub const CUR_SIZE_ALL_MEM_TABLES: &str = "rocksdb.cur-size-all-mem-tables";
+pub const SIZE_ALL_MEM_TABLES: &str = "rocksdb.size-all-mem-tables";
+pub const NUM_ENTRIES_ACTIVE_MEM_TABLE: &str = "rocksdb.num-entries-active-mem-table";
+pub const NUM_ENTRIES_IMM_MEM_TABLES: &str = "rocksdb.num-entries-imm-mem-tables";
+pub const NUM_DELETES_ACTIVE_MEM_TABLE: &str = "rocksdb.num-deletes-active-mem-table";
+pub const NUM_DELETES_IMM_MEM_TABLES: &str = "rocksdb.num-deletes-imm-mem-tables";
+pub const ESTIMATE_NUM_KEYS: &str = "rocksdb.estimate-num-keys";
+pub const ESTIMATE_TABLE_READERS_MEM: &str = "rocksdb.estimate-table-readers-mem";
+pub const IS_FILE_DELETIONS_ENABLED: &str = "rocksdb.is-file-deletions-enabled";
+pub const NUM_SNAPSHOTS: &str = "rocksdb.num-snapshots";
+pub const OLDEST_SNAPSHOT_TIME: &str = "rocksdb.oldest-snapshot-time";
+pub const OLDEST_SNAPSHOT_SEQUENCE: &str = "rocksdb.oldest-snapshot-sequence";
+pub const NUM_LIVE_VERSIONS: &str = "rocksdb.num-live-versions";
+pub const CURRENT_SUPER_VERSION_NUMBER: &str = "rocksdb.current-super-version-number";
+pub const ESTIMATE_LIVE_DATA_SIZE: &str = "rocksdb.estimate-live-data-size";
+pub const MIN_LOG_NUMBER_TO_KEEP: &str = "rocksdb.min-log-number-to-keep";
+pub const MIN_OBSOLETE_SST_NUMBER_TO_KEEP: &str = "rocksdb.min-obsolete-sst-number-to-keep";
+pub const TOTAL_SST_FILES_SIZE: &str = "rocksdb.total-sst-files-size";
+pub const LIVE_SST_FILES_SIZE: &str = "rocksdb.live-sst-files-size";
+pub const BASE_LEVEL: &str = "rocksdb.base-level";
+pub const ESTIMATE_PENDING_COMPACTION_BYTES: &str = "rocksdb.estimate-pending-compaction-bytes";
+/// String or map property.
+pub const AGGREGATED_TABLE_PROPERTIES: &str = "rocksdb.aggregated-table-properties";
+/// Append the level number. String or map property.
+pub const AGGREGATED_TABLE_PROPERTIES_AT_LEVEL: &str =
+    "rocksdb.aggregated-table-properties-at-level";
+pub const ACTUAL_DELAYED_WRITE_RATE: &str = "rocksdb.actual-delayed-write-rate";
+pub const IS_WRITE_STOPPED: &str = "rocksdb.is-write-stopped";
+pub const ESTIMATE_OLDEST_KEY_TIME: &str = "rocksdb.estimate-oldest-key-time";
+pub const BLOCK_CACHE_CAPACITY: &str = "rocksdb.block-cache-capacity";
+pub const BLOCK_CACHE_USAGE: &str = "rocksdb.block-cache-usage";
+pub const BLOCK_CACHE_PINNED_USAGE: &str = "rocksdb.block-cache-pinned-usage";
+pub const OPTIONS_STATISTICS: &str = "rocksdb.options-statistics";
+pub const NUM_BLOB_FILES: &str = "rocksdb.num-blob-files";
+pub const BLOB_STATS: &str = "rocksdb.blob-stats";
+pub const TOTAL_BLOB_FILE_SIZE: &str = "rocksdb.total-blob-file-size";
+pub const LIVE_BLOB_FILE_SIZE: &str = "rocksdb.live-blob-file-size";
+pub const LIVE_BLOB_FILE_GARBAGE_SIZE: &str = "rocksdb.live-blob-file-garbage-size";
+pub const BLOB_CACHE_CAPACITY: &str = "rocksdb.blob-cache-capacity";
+pub const BLOB_CACHE_USAGE: &str = "rocksdb.blob-cache-usage";
+pub const BLOB_CACHE_PINNED_USAGE: &str = "rocksdb.blob-cache-pinned-usage";
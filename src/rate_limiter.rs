@@ -0,0 +1,93 @@
+use std::{pin::Pin, sync::Arc, time::Duration};
+
+use autorocks_sys::{
+    new_rate_limiter,
+    rocksdb::{Env_IOPriority, RateLimiter_Mode},
+    RateLimiterWrapper,
+};
+use moveit::Emplace;
+
+/// Limits the rate of background I/O, i.e. flushes and compactions, and
+/// writes with `WriteOpts::rate_limiter_priority`. Set with
+/// `DbOptions::rate_limiter`.
+///
+/// Clones refer to the same limiter, so one limiter can be shared by several
+/// databases to limit their total I/O.
+#[derive(Clone)]
+pub struct RateLimiter {
+    inner: Arc<RateLimiterWrapper>,
+}
+
+impl RateLimiter {
+    /// Limit writes to `bytes_per_second`, refilled every 100ms, with the
+    /// default fairness.
+    pub fn new(bytes_per_second: i64) -> Self {
+        Self::with_options(
+            bytes_per_second,
+            Duration::from_millis(100),
+            10,
+            RateLimiter_Mode::kWritesOnly,
+            false,
+        )
+    }
+
+    /// * `refill_period`: tokens are refilled at this interval. Shorter
+    ///   periods give smoother I/O, at the cost of more CPU.
+    /// * `fairness`: low priority requests are served first with a chance of
+    ///   `1 / fairness`, so they are not starved by high priority ones.
+    /// * `mode`: which kind of I/O is limited.
+    /// * `auto_tuned`: adjust the rate dynamically, between
+    ///   `bytes_per_second / 20` and `bytes_per_second`, to the demand.
+    pub fn with_options(
+        bytes_per_second: i64,
+        refill_period: Duration,
+        fairness: i32,
+        mode: RateLimiter_Mode,
+        auto_tuned: bool,
+    ) -> Self {
+        let refill_period_us = refill_period
+            .as_micros()
+            .try_into()
+            .expect("refill period too large");
+        let limiter = Arc::emplace(new_rate_limiter(
+            bytes_per_second,
+            refill_period_us,
+            fairness,
+            mode,
+            auto_tuned,
+        ));
+        Self {
+            inner: Pin::into_inner(limiter),
+        }
+    }
+
+    /// Change the rate. Takes effect for all databases using the limiter.
+    pub fn set_bytes_per_second(&self, bytes_per_second: i64) {
+        self.inner.set_bytes_per_second(bytes_per_second);
+    }
+
+    pub fn bytes_per_second(&self) -> i64 {
+        self.inner.bytes_per_second()
+    }
+
+    /// Maximum number of bytes that can be granted in a single request.
+    pub fn single_burst_bytes(&self) -> i64 {
+        self.inner.single_burst_bytes()
+    }
+
+    /// Total bytes that went through the limiter at `pri`, or at all
+    /// priorities for `Env_IOPriority::IO_TOTAL`.
+    pub fn total_bytes_through(&self, pri: Env_IOPriority) -> i64 {
+        self.inner.total_bytes_through(pri)
+    }
+
+    /// Total number of requests at `pri`, or at all priorities for
+    /// `Env_IOPriority::IO_TOTAL`.
+    pub fn total_requests(&self, pri: Env_IOPriority) -> i64 {
+        self.inner.total_requests(pri)
+    }
+
+    pub fn as_inner(&self) -> &RateLimiterWrapper {
+        &self.inner
+    }
+}
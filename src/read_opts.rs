@@ -0,0 +1,99 @@
+use std::{
+    pin::Pin,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use autorocks_sys::{rocksdb::ReadOptions, ReadOptionsWrapper};
+use moveit::Emplace;
+
+/// Options for reads. Pass `as_inner()` to methods like
+/// `TransactionDb::get_with_options` and `TransactionDb::iter_with_options`.
+pub struct ReadOpts {
+    inner: Pin<Box<ReadOptionsWrapper>>,
+}
+
+impl Default for ReadOpts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadOpts {
+    pub fn new() -> Self {
+        Self {
+            inner: Box::emplace(ReadOptionsWrapper::new()),
+        }
+    }
+
+    /// Fail with a `TimedOut` error if the read is not done within `timeout`
+    /// from now. For iterators, the deadline applies to every seek and step,
+    /// check `DbIterator::status` when iteration stops.
+    ///
+    /// Best effort: it is only checked when reading from files, not for
+    /// entries in memtables or the block cache.
+    ///
+    /// The deadline is fixed when this is called, not when the options are
+    /// used. Call it again before reusing the options for another read, or
+    /// the read may time out right away.
+    pub fn deadline(&mut self, timeout: Duration) -> &mut Self {
+        let deadline = SystemTime::now() + timeout;
+        let micros = deadline
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before epoch")
+            .as_micros();
+        self.inner
+            .as_mut()
+            .set_deadline(micros.try_into().expect("deadline too large"));
+        self
+    }
+
+    /// Fail with a `TimedOut` error if a single file read takes longer than
+    /// this. Only effective if the file system supports timeouts.
+    pub fn io_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.inner.as_mut().set_io_timeout(
+            timeout
+                .as_micros()
+                .try_into()
+                .expect("io timeout too large"),
+        );
+        self
+    }
+
+    /// Only return keys with the same prefix as the seek key, as determined
+    /// by the prefix extractor.
+    pub fn prefix_same_as_start(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_prefix_same_as_start(val);
+        self
+    }
+
+    /// Ignore the prefix extractor and bloom filters, and iterate over all
+    /// keys in order.
+    pub fn total_order_seek(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_total_order_seek(val);
+        self
+    }
+
+    pub fn auto_prefix_mode(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_auto_prefix_mode(val);
+        self
+    }
+
+    /// Read as of `ts`, for column families with
+    /// `CfOptions::u64_timestamp_comparator`.
+    pub fn timestamp(&mut self, ts: u64) -> &mut Self {
+        self.inner.as_mut().set_timestamp(ts);
+        self
+    }
+
+    /// Make iterators return every version with a timestamp from `ts` up to
+    /// `timestamp`, newest first, instead of only the newest one. See
+    /// `DbIterator::timestamp`.
+    pub fn iter_start_ts(&mut self, ts: u64) -> &mut Self {
+        self.inner.as_mut().set_iter_start_ts(ts);
+        self
+    }
+
+    pub fn as_inner(&self) -> &ReadOptions {
+        (*self.inner).as_ref()
+    }
+}
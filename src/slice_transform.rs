@@ -0,0 +1,84 @@
+use std::pin::Pin;
+
+use autocxx::c_void;
+use autorocks_sys::{
+    new_capped_prefix_transform, new_fixed_prefix_transform, new_rust_slice_transform,
+    rocksdb::Slice, SliceTransformWrapper,
+};
+use moveit::Emplace;
+
+use crate::slice::as_rust_slice1;
+
+/// A prefix extractor implemented in rust.
+///
+/// Methods are called from RocksDB threads. They must not panic.
+pub trait CustomSliceTransform: Send + Sync + 'static {
+    /// Name of the transform. It is persisted in the options file and
+    /// table properties, so it should change when the transform changes.
+    fn name(&self) -> &str;
+
+    /// Extract a prefix from a key in the domain.
+    fn transform<'a>(&self, key: &'a [u8]) -> &'a [u8];
+
+    /// Whether `key` has a prefix.
+    fn in_domain(&self, key: &[u8]) -> bool;
+}
+
+/// Prefix extractor, set with `CfOptions::prefix_extractor`.
+pub struct SliceTransform {
+    inner: Pin<Box<SliceTransformWrapper>>,
+}
+
+impl SliceTransform {
+    /// Use the first `prefix_len` bytes as the prefix. Shorter keys are not in
+    /// the domain.
+    pub fn fixed_prefix(prefix_len: usize) -> Self {
+        Self {
+            inner: Box::emplace(new_fixed_prefix_transform(prefix_len)),
+        }
+    }
+
+    /// Use the first `cap_len` bytes as the prefix, or the whole key if it is
+    /// shorter.
+    pub fn capped_prefix(cap_len: usize) -> Self {
+        Self {
+            inner: Box::emplace(new_capped_prefix_transform(cap_len)),
+        }
+    }
+
+    pub fn custom<T: CustomSliceTransform>(transform: T) -> Self {
+        let ctx = Box::into_raw(Box::new(transform));
+        // The name may borrow from the transform, so take it after boxing.
+        // It is copied before returning.
+        let name: Slice = unsafe { (*ctx).name() }.as_bytes().into();
+        Self {
+            inner: Box::emplace(unsafe {
+                new_rust_slice_transform(
+                    name,
+                    ctx as *mut c_void,
+                    transform_fn::<T> as *mut c_void,
+                    in_domain_fn::<T> as *mut c_void,
+                    drop_fn::<T> as *mut c_void,
+                )
+            }),
+        }
+    }
+
+    pub fn as_inner(&self) -> &SliceTransformWrapper {
+        &self.inner
+    }
+}
+
+unsafe extern "C" fn transform_fn<T: CustomSliceTransform>(ctx: *mut c_void, key: Slice) -> Slice {
+    let t = &*(ctx as *const T);
+    t.transform(as_rust_slice1(key)).into()
+}
+
+unsafe extern "C" fn in_domain_fn<T: CustomSliceTransform>(ctx: *mut c_void, key: Slice) -> bool {
+    let t = &*(ctx as *const T);
+    t.in_domain(as_rust_slice1(key))
+}
+
+unsafe extern "C" fn drop_fn<T: CustomSliceTransform>(ctx: *mut c_void) {
+    drop(Box::from_raw(ctx as *mut T));
+}
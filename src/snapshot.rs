@@ -39,6 +39,7 @@ impl Snapshot {
         let iter = self.db.iter_with_options((*options).as_ref(), col, dir);
         DbIterator {
             inner: iter.inner,
+            options: iter.options,
             just_seeked: iter.just_seeked,
             direction: iter.direction,
             phantom: PhantomData,
@@ -85,6 +86,7 @@ impl SharedSnapshot {
         let iter = self.db.iter_with_options((*options).as_ref(), col, dir);
         DbIterator {
             inner: iter.inner,
+            options: iter.options,
             just_seeked: iter.just_seeked,
             direction: iter.direction,
             phantom: PhantomData,
@@ -126,6 +128,7 @@ impl<'a> SnapshotRef<'a> {
         let iter = self.tx.iter_with_options((*options).as_ref(), col, dir);
         DbIterator {
             inner: iter.inner,
+            options: iter.options,
             just_seeked: iter.just_seeked,
             direction: iter.direction,
             phantom: PhantomData,
@@ -0,0 +1,225 @@
+use std::{
+    ffi::OsStr,
+    os::unix::prelude::OsStrExt,
+    path::{Path, PathBuf},
+    pin::Pin,
+};
+
+use autorocks_sys::{
+    rocksdb::ReadOptions, ExternalSstFileInfoPod, IngestOptionsWrapper, SstFileReaderWrapper,
+    SstFileWriterWrapper,
+};
+use moveit::{moveit, Emplace};
+
+use crate::{
+    into_result, slice::as_rust_slice1, CfOptions, DbIterator, Direction, Result, TableProperties,
+};
+
+/// Information about a file created by `SstFileWriter`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalSstFileInfo {
+    pub file_path: PathBuf,
+    pub smallest_key: Box<[u8]>,
+    pub largest_key: Box<[u8]>,
+    pub smallest_range_del_key: Box<[u8]>,
+    pub largest_range_del_key: Box<[u8]>,
+    pub sequence_number: u64,
+    /// File size in bytes.
+    pub file_size: u64,
+    pub num_entries: u64,
+    pub num_range_del_entries: u64,
+    pub version: i32,
+}
+
+impl ExternalSstFileInfo {
+    fn from_pod(pod: ExternalSstFileInfoPod) -> Self {
+        unsafe {
+            Self {
+                file_path: OsStr::from_bytes(as_rust_slice1(pod.file_path)).into(),
+                smallest_key: as_rust_slice1(pod.smallest_key).into(),
+                largest_key: as_rust_slice1(pod.largest_key).into(),
+                smallest_range_del_key: as_rust_slice1(pod.smallest_range_del_key).into(),
+                largest_range_del_key: as_rust_slice1(pod.largest_range_del_key).into(),
+                sequence_number: pod.sequence_number,
+                file_size: pod.file_size,
+                num_entries: pod.num_entries,
+                num_range_del_entries: pod.num_range_del_entries,
+                version: pod.version,
+            }
+        }
+    }
+}
+
+/// Writes an SST file that can be ingested with
+/// `TransactionDb::ingest_external_files`.
+///
+/// Keys must be added in ascending order of the comparator.
+pub struct SstFileWriter {
+    inner: Pin<Box<SstFileWriterWrapper>>,
+}
+
+impl SstFileWriter {
+    /// Create the file at `path`. `options` should match the options of the
+    /// column family the file is ingested into.
+    pub fn open(options: &CfOptions, path: &Path) -> Result<Self> {
+        let mut inner = Box::emplace(SstFileWriterWrapper::new());
+        moveit! {
+            let status = inner
+                .as_mut()
+                .open(options.as_inner(), path.as_os_str().as_bytes().into());
+        }
+        into_result(&status)?;
+        Ok(Self { inner })
+    }
+
+    pub fn put(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        moveit! {
+            let status = self.inner.as_mut().put(&key.into(), &value.into());
+        }
+        into_result(&status)
+    }
+
+    pub fn merge(&mut self, key: &[u8], value: &[u8]) -> Result<()> {
+        moveit! {
+            let status = self.inner.as_mut().merge(&key.into(), &value.into());
+        }
+        into_result(&status)
+    }
+
+    pub fn delete(&mut self, key: &[u8]) -> Result<()> {
+        moveit! {
+            let status = self.inner.as_mut().del(&key.into());
+        }
+        into_result(&status)
+    }
+
+    /// Delete keys in `[begin_key, end_key)`. Range deletions don't need to
+    /// be ordered with other entries.
+    pub fn delete_range(&mut self, begin_key: &[u8], end_key: &[u8]) -> Result<()> {
+        moveit! {
+            let status = self.inner.as_mut().delete_range(&begin_key.into(), &end_key.into());
+        }
+        into_result(&status)
+    }
+
+    /// Size of the file written so far.
+    pub fn file_size(&self) -> u64 {
+        self.inner.file_size()
+    }
+
+    /// Finish writing the file. Fails if no entries were added.
+    pub fn finish(mut self) -> Result<ExternalSstFileInfo> {
+        moveit! {
+            let status = self.inner.as_mut().finish();
+        }
+        into_result(&status)?;
+        Ok(ExternalSstFileInfo::from_pod(self.inner.info_pod()))
+    }
+}
+
+/// Reads an SST file without opening a database.
+pub struct SstFileReader {
+    inner: Pin<Box<SstFileReaderWrapper>>,
+}
+
+impl SstFileReader {
+    /// `options` should match the options the file was written with, e.g.
+    /// the comparator and prefix extractor.
+    pub fn open(options: &CfOptions, path: &Path) -> Result<Self> {
+        let mut inner = Box::emplace(SstFileReaderWrapper::new());
+        moveit! {
+            let status = inner
+                .as_mut()
+                .open(options.as_inner(), path.as_os_str().as_bytes().into());
+        }
+        into_result(&status)?;
+        Ok(Self { inner })
+    }
+
+    pub fn iter(&self, dir: Direction) -> DbIterator<&'_ Self> {
+        moveit! {
+            let options = ReadOptions::new();
+        }
+        self.iter_with_options(&options, dir)
+    }
+
+    pub fn iter_with_options(&self, options: &ReadOptions, dir: Direction) -> DbIterator<&'_ Self> {
+        unsafe { DbIterator::with_options(options, dir, |options| self.inner.iter(options)) }
+    }
+
+    pub fn table_properties(&self) -> TableProperties {
+        TableProperties::from_pod(self.inner.table_properties())
+    }
+
+    /// Read all blocks and verify their checksums.
+    pub fn verify_checksum(&self) -> Result<()> {
+        moveit! {
+            let status = self.inner.verify_checksum();
+        }
+        into_result(&status)
+    }
+}
+
+/// Options for `TransactionDb::ingest_external_files`.
+pub struct IngestOpts {
+    inner: Pin<Box<IngestOptionsWrapper>>,
+}
+
+impl Default for IngestOpts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IngestOpts {
+    pub fn new() -> Self {
+        Self {
+            inner: Box::emplace(IngestOptionsWrapper::new()),
+        }
+    }
+
+    /// Move the files instead of copying them. Falls back to copying if the
+    /// files cannot be hard linked.
+    pub fn move_files(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_move_files(val);
+        self
+    }
+
+    /// Snapshots taken before ingestion don't see the ingested keys
+    /// (the default).
+    pub fn snapshot_consistency(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_snapshot_consistency(val);
+        self
+    }
+
+    /// Allow assigning a global sequence number to the files, needed when
+    /// they overlap existing keys (the default). If false, such ingestion
+    /// fails.
+    pub fn allow_global_seqno(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_allow_global_seqno(val);
+        self
+    }
+
+    /// Allow flushing the memtable if it overlaps the files (the default).
+    pub fn allow_blocking_flush(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_allow_blocking_flush(val);
+        self
+    }
+
+    /// Ingest the files into the bottommost level, below existing keys, so
+    /// that existing keys take precedence. Requires
+    /// `DbOptions::allow_ingest_behind`.
+    pub fn ingest_behind(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_ingest_behind(val);
+        self
+    }
+
+    pub fn verify_checksums_before_ingest(&mut self, val: bool) -> &mut Self {
+        self.inner.as_mut().set_verify_checksums_before_ingest(val);
+        self
+    }
+
+    pub(crate) fn as_inner(&self) -> &IngestOptionsWrapper {
+        &self.inner
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap, ffi::OsStr, os::unix::prelude::OsStrExt, path::PathBuf, pin::Pin,
+    sync::Arc,
+};
+
+use autorocks_sys::{new_sst_file_manager, SstFileManagerWrapper, TrackedFilesWrapper};
+use moveit::{moveit, Emplace};
+
+use crate::{slice::as_rust_slice1, Env};
+
+/// Tracks the SST and blob files of databases, limits their total size and
+/// the rate at which they are deleted. Set with `DbOptions::sst_file_manager`.
+///
+/// Clones refer to the same manager, so one manager can be shared by several
+/// databases.
+#[derive(Clone)]
+pub struct SstFileManager {
+    inner: Arc<SstFileManagerWrapper>,
+}
+
+impl Default for SstFileManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SstFileManager {
+    /// No space limit and no deletion rate limit.
+    pub fn new() -> Self {
+        Self::with_env(&Env::new())
+    }
+
+    /// Access files through `env`, which should be the env of the databases
+    /// using the manager, see `DbOptions::env`.
+    pub fn with_env(env: &Env) -> Self {
+        Self {
+            inner: Pin::into_inner(Arc::emplace(new_sst_file_manager(env.as_inner(), 0))),
+        }
+    }
+
+    /// Once the total size of tracked files exceeds this, flushes and
+    /// compactions fail with a `NoSpace` error and the database stops
+    /// accepting writes. 0 (the default) means no limit.
+    pub fn set_max_allowed_space_usage(&self, max_allowed_space: u64) {
+        self.inner.set_max_allowed_space_usage(max_allowed_space);
+    }
+
+    /// Space to leave free for other uses. Compactions are not started if
+    /// their estimated output would exceed the limit minus this.
+    pub fn set_compaction_buffer_size(&self, compaction_buffer_size: u64) {
+        self.inner
+            .set_compaction_buffer_size(compaction_buffer_size);
+    }
+
+    pub fn is_max_allowed_space_reached(&self) -> bool {
+        self.inner.is_max_allowed_space_reached()
+    }
+
+    /// Also counts the estimated output of running compactions.
+    pub fn is_max_allowed_space_reached_including_compactions(&self) -> bool {
+        self.inner
+            .is_max_allowed_space_reached_including_compactions()
+    }
+
+    /// Total size of tracked files in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.inner.total_size()
+    }
+
+    /// Paths and sizes of tracked files.
+    pub fn tracked_files(&self) -> HashMap<PathBuf, u64> {
+        moveit! {
+            let mut files = TrackedFilesWrapper::new();
+        }
+        unsafe {
+            self.inner.tracked_files(files.as_mut().get_unchecked_mut());
+        }
+        (0..files.len())
+            .map(|i| {
+                let path = OsStr::from_bytes(unsafe { as_rust_slice1(files.path(i)) });
+                (path.into(), files.size(i))
+            })
+            .collect()
+    }
+
+    pub fn delete_rate_bytes_per_sec(&self) -> i64 {
+        self.inner.delete_rate_bytes_per_sec()
+    }
+
+    /// Obsolete files are moved to trash and deleted at this rate, to avoid
+    /// I/O spikes. 0 (the default) means they are deleted right away.
+    pub fn set_delete_rate_bytes_per_sec(&self, delete_rate: i64) {
+        self.inner.set_delete_rate_bytes_per_sec(delete_rate);
+    }
+
+    pub fn max_trash_db_ratio(&self) -> f64 {
+        self.inner.max_trash_db_ratio()
+    }
+
+    /// If trash exceeds this fraction of the total size, files are deleted
+    /// right away regardless of the deletion rate. Default 0.25.
+    pub fn set_max_trash_db_ratio(&self, ratio: f64) {
+        self.inner.set_max_trash_db_ratio(ratio);
+    }
+
+    /// Size of files waiting to be deleted, in bytes.
+    pub fn total_trash_size(&self) -> u64 {
+        self.inner.total_trash_size()
+    }
+
+    pub fn as_inner(&self) -> &SstFileManagerWrapper {
+        &self.inner
+    }
+}
@@ -0,0 +1,100 @@
+use std::{fmt, pin::Pin, sync::Arc};
+
+use autorocks_sys::{
+    new_statistics,
+    rocksdb::{Histograms, StatsLevel, Tickers},
+    StatisticsWrapper,
+};
+use moveit::{moveit, Emplace};
+
+use crate::{into_result, Result};
+
+/// Database statistics: tickers (counters) and histograms.
+///
+/// Set with `DbOptions::statistics`. Clones refer to the same statistics,
+/// so one object can be shared by several databases.
+#[derive(Clone)]
+pub struct Statistics {
+    inner: Arc<StatisticsWrapper>,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Statistics {
+    /// Statistics with `StatsLevel::kExceptDetailedTimers`.
+    pub fn new() -> Self {
+        Self {
+            inner: Pin::into_inner(Arc::emplace(new_statistics())),
+        }
+    }
+
+    pub fn with_stats_level(level: StatsLevel) -> Self {
+        let statistics = Self::new();
+        statistics.set_stats_level(level);
+        statistics
+    }
+
+    pub fn stats_level(&self) -> StatsLevel {
+        self.inner.stats_level()
+    }
+
+    pub fn set_stats_level(&self, level: StatsLevel) {
+        self.inner.set_stats_level(level);
+    }
+
+    pub fn ticker(&self, ticker: Tickers) -> u64 {
+        self.inner.ticker(ticker)
+    }
+
+    pub fn histogram(&self, histogram: Histograms) -> HistogramData {
+        let data = self.inner.histogram(histogram);
+        HistogramData {
+            median: data.median,
+            percentile95: data.percentile95,
+            percentile99: data.percentile99,
+            average: data.average,
+            standard_deviation: data.standard_deviation,
+            max: data.max,
+            min: data.min,
+            count: data.count,
+            sum: data.sum,
+        }
+    }
+
+    /// Reset all tickers and histograms to zero.
+    pub fn reset(&self) -> Result<()> {
+        moveit! {
+            let status = self.inner.reset();
+        }
+        into_result(&status)
+    }
+
+    pub(crate) fn as_inner(&self) -> &StatisticsWrapper {
+        &self.inner
+    }
+}
+
+/// Dump of all tickers and histograms.
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.inner.dump().to_string_lossy())
+    }
+}
+
+/// Summary of a histogram. Times are in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramData {
+    pub median: f64,
+    pub percentile95: f64,
+    pub percentile99: f64,
+    pub average: f64,
+    pub standard_deviation: f64,
+    pub max: f64,
+    pub min: f64,
+    pub count: u64,
+    pub sum: u64,
+}
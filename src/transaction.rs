@@ -99,6 +99,30 @@ impl Transaction {
         }
     }
 
+    /// Timestamp of the writes to column families with
+    /// `CfOptions::u64_timestamp_comparator`. Must be set before committing
+    /// such writes.
+    ///
+    /// Transactions with a snapshot, like those from
+    /// `TransactionDb::begin_transaction`, must also call
+    /// `set_read_timestamp_for_validation` before writing to such column
+    /// families.
+    pub fn set_commit_timestamp(&mut self, ts: u64) -> Result<()> {
+        moveit! {
+            let status = self.as_inner_mut().set_commit_timestamp(ts);
+        }
+        into_result(&status)
+    }
+
+    /// Make writes to keys with versions newer than `ts` fail, like a
+    /// transaction snapshot does for column families without timestamps.
+    pub fn set_read_timestamp_for_validation(&mut self, ts: u64) -> Result<()> {
+        moveit! {
+            let status = self.as_inner_mut().set_read_timestamp_for_validation(ts);
+        }
+        into_result(&status)
+    }
+
     pub fn iter(&self, col: usize, dir: Direction) -> DbIterator<&'_ Self> {
         moveit! {
             let options = ReadOptions::new();
@@ -114,7 +138,9 @@ impl Transaction {
     ) -> DbIterator<&'a Self> {
         let cf = self.db.as_inner().get_cf(col);
         assert!(!cf.is_null());
-        unsafe { DbIterator::new(self.as_inner().iter(options, cf), dir) }
+        unsafe {
+            DbIterator::with_options(options, dir, |options| self.as_inner().iter(options, cf))
+        }
     }
 
     pub fn rollback(&mut self) -> Result<()> {
@@ -0,0 +1,171 @@
+use std::{pin::Pin, sync::Arc, time::Duration};
+
+use autorocks_sys::{
+    rocksdb::{PinnableSlice, ReadOptions, WriteOptions},
+    DbOptionsWrapper, TtlDbWrapper,
+};
+use moveit::{moveit, Emplace};
+
+use crate::{
+    cf_options::whole_secs, into_result, slice::as_rust_slice, DbIterator, Direction, Result,
+};
+
+/// A database where entries expire after a per column family TTL.
+///
+/// Each value is stored with its write time appended. Expired entries may
+/// still be returned until they are removed by compaction.
+#[derive(Clone)]
+pub struct TtlDb {
+    inner: Arc<TtlDbWrapper>,
+}
+
+impl TtlDb {
+    pub(crate) fn open(options: &DbOptionsWrapper, ttls: &[Option<Duration>]) -> Result<TtlDb> {
+        let ttls: Vec<i32> = (0..options.cf_len())
+            .map(|i| ttls.get(i).copied().flatten().map_or(0, ttl_secs))
+            .collect();
+        let db = Arc::emplace(TtlDbWrapper::new());
+        let mut db = Pin::into_inner(db);
+        let db_mut = Arc::get_mut(&mut db).unwrap();
+        moveit! {
+            let status = unsafe { Pin::new(db_mut).open(options, ttls.as_ptr(), ttls.len()) };
+        }
+        into_result(&status)?;
+        Ok(TtlDb { inner: db })
+    }
+
+    pub fn default_col(&self) -> usize {
+        self.inner.default_col()
+    }
+
+    /// Change the TTL of a column family. `None` means entries never expire.
+    /// Rounded up to whole seconds.
+    ///
+    /// # Panics
+    ///
+    /// If `ttl` is zero (use `None` instead) or too large.
+    pub fn set_ttl(&self, col: usize, ttl: Option<Duration>) {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        unsafe { self.inner.set_ttl(cf, ttl.map_or(0, ttl_secs)) };
+    }
+
+    pub fn put(&self, col: usize, key: &[u8], value: &[u8]) -> Result<()> {
+        moveit! {
+            let options = WriteOptions::new();
+        }
+        self.put_with_options(&options, col, key, value)
+    }
+
+    pub fn put_with_options(
+        &self,
+        options: &WriteOptions,
+        col: usize,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<()> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let status = unsafe { self.inner.put(options, cf, &key.into(), &value.into()) };
+        }
+        into_result(&status)
+    }
+
+    pub fn delete(&self, col: usize, key: &[u8]) -> Result<()> {
+        moveit! {
+            let options = WriteOptions::new();
+        }
+        self.delete_with_options(&options, col, key)
+    }
+
+    pub fn delete_with_options(
+        &self,
+        options: &WriteOptions,
+        col: usize,
+        key: &[u8],
+    ) -> Result<()> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let status = unsafe { self.inner.del(options, cf, &key.into()) };
+        }
+        into_result(&status)
+    }
+
+    pub fn get<'b>(
+        &self,
+        col: usize,
+        key: &[u8],
+        buf: Pin<&'b mut PinnableSlice>,
+    ) -> Result<Option<&'b [u8]>> {
+        moveit! {
+            let options = ReadOptions::new();
+        }
+        self.get_with_options(&options, col, key, buf)
+    }
+
+    pub fn get_with_options<'b>(
+        &self,
+        options: &ReadOptions,
+        col: usize,
+        key: &[u8],
+        buf: Pin<&'b mut PinnableSlice>,
+    ) -> Result<Option<&'b [u8]>> {
+        let slice = unsafe { buf.get_unchecked_mut() };
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        moveit! {
+            let status = unsafe { self.inner.get(options, cf, &key.into(), slice) };
+        }
+        if status.IsNotFound() {
+            return Ok(None);
+        }
+        into_result(&status)?;
+        Ok(Some(as_rust_slice(slice)))
+    }
+
+    pub fn get_int_property(&self, col: usize, property: &str) -> Option<u64> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        let mut val = 0;
+        let got = unsafe {
+            self.inner
+                .get_int_property(cf, &property.as_bytes().into(), &mut val)
+        };
+        got.then_some(val)
+    }
+
+    pub fn iter(&self, col: usize, dir: Direction) -> DbIterator<&'_ Self> {
+        moveit! {
+            let options = ReadOptions::new();
+        }
+        self.iter_with_options(&options, col, dir)
+    }
+
+    pub fn iter_with_options<'a>(
+        &'a self,
+        options: &ReadOptions,
+        col: usize,
+        dir: Direction,
+    ) -> DbIterator<&'a Self> {
+        let cf = self.inner.get_cf(col);
+        assert!(!cf.is_null());
+        unsafe {
+            DbIterator::with_options(options, dir, |options| self.as_inner().iter(options, cf))
+        }
+    }
+
+    pub fn as_inner(&self) -> &TtlDbWrapper {
+        &self.inner
+    }
+}
+
+fn ttl_secs(ttl: Duration) -> i32 {
+    // RocksDB treats a zero TTL as no expiry.
+    assert!(
+        !ttl.is_zero(),
+        "ttl must not be zero, use None for no expiry"
+    );
+    whole_secs(ttl).try_into().expect("ttl too large")
+}
@@ -0,0 +1,58 @@
+use std::pin::Pin;
+
+use autorocks_sys::TransactionLogIteratorWrapper;
+use moveit::moveit;
+
+use crate::{into_result, Result, TransactionDb, WriteBatch};
+
+/// Iterator over the write batches in the WAL, returned by
+/// `TransactionDb::get_updates_since`.
+///
+/// Yields `(sequence_number, batch)` pairs, where `sequence_number` is that of
+/// the first entry in the batch. Iteration stops at the end of the WAL as of
+/// its creation, or after the first error.
+pub struct WalIterator {
+    inner: Pin<Box<TransactionLogIteratorWrapper>>,
+    db: TransactionDb,
+    started: bool,
+    done: bool,
+}
+
+impl WalIterator {
+    pub(crate) fn new(inner: Pin<Box<TransactionLogIteratorWrapper>>, db: TransactionDb) -> Self {
+        Self {
+            inner,
+            db,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for WalIterator {
+    type Item = Result<(u64, WriteBatch)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // The iterator is already positioned at the first batch.
+        if self.started {
+            self.inner.as_mut().next();
+        }
+        self.started = true;
+        if !self.inner.valid() {
+            self.done = true;
+            moveit! {
+                let status = self.inner.status();
+            }
+            return into_result(&status).err().map(Err);
+        }
+        let mut sequence_number = 0;
+        let batch = WriteBatch {
+            inner: unsafe { self.inner.as_mut().take_batch(&mut sequence_number) },
+            db: self.db.clone(),
+        };
+        Some(Ok((sequence_number, batch)))
+    }
+}
@@ -0,0 +1,26 @@
+use autorocks_sys::{rocksdb::Slice, PinnableWideColumnsWrapper, WideColumnPod};
+
+use crate::slice::as_rust_slice1;
+
+pub(crate) fn to_slices<N: AsRef<[u8]>, V: AsRef<[u8]>>(
+    columns: &[(N, V)],
+) -> (Vec<Slice>, Vec<Slice>) {
+    columns
+        .iter()
+        .map(|(n, v)| (n.as_ref().into(), v.as_ref().into()))
+        .unzip()
+}
+
+pub(crate) fn to_vec(columns: &PinnableWideColumnsWrapper) -> Vec<(Vec<u8>, Vec<u8>)> {
+    (0..columns.len())
+        .map(|i| {
+            let (name, value) = unsafe { from_pod(columns.get(i)) };
+            (name.to_vec(), value.to_vec())
+        })
+        .collect()
+}
+
+/// Safety: the slices must be valid for 'a.
+pub(crate) unsafe fn from_pod<'a>(column: WideColumnPod) -> (&'a [u8], &'a [u8]) {
+    (as_rust_slice1(column.name), as_rust_slice1(column.value))
+}
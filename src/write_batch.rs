@@ -1,9 +1,30 @@
-use std::{hint::unreachable_unchecked, pin::Pin};
+use std::{collections::HashMap, hint::unreachable_unchecked, pin::Pin, ptr};
 
-use autocxx::prelude::UniquePtr;
+use autocxx::{c_void, prelude::UniquePtr};
+use autorocks_sys::{iterate_write_batch, rocksdb::Slice, write_batch_put_entity, WideColumnPod};
 use moveit::moveit;
 
-use crate::{into_result, Result, TransactionDb};
+use crate::{into_result, slice::as_rust_slice1, wide_columns, Result, TransactionDb};
+
+/// Visits the entries of a `WriteBatch`, see `WriteBatch::iterate`.
+///
+/// Column families are given by index, as elsewhere. Methods must not panic.
+pub trait WriteBatchHandler {
+    fn put(&mut self, col: usize, key: &[u8], value: &[u8]);
+
+    fn delete(&mut self, col: usize, key: &[u8]);
+
+    /// Delete entries in the range of ["begin_key", "end_key").
+    fn delete_range(&mut self, col: usize, begin_key: &[u8], end_key: &[u8]);
+
+    fn merge(&mut self, _col: usize, _key: &[u8], _value: &[u8]) {}
+
+    /// Blob added with `PutLogData`, which is only written to the WAL.
+    fn log_data(&mut self, _blob: &[u8]) {}
+
+    /// Entity added with `put_entity`. Columns are sorted by name.
+    fn put_entity(&mut self, _col: usize, _key: &[u8], _columns: &[(&[u8], &[u8])]) {}
+}
 
 pub struct WriteBatch {
     pub(crate) inner: UniquePtr<autorocks_sys::rocksdb::WriteBatch>,
@@ -20,6 +41,29 @@ impl WriteBatch {
         into_result(&status)
     }
 
+    /// Write an entity with named wide columns, see
+    /// `TransactionDb::put_entity`.
+    ///
+    /// The keys of a batch with entities are locked with a separate
+    /// transaction when it is written, since RocksDB 7.7 can't lock them
+    /// itself. Range deletions aren't supported in such a batch.
+    pub fn put_entity<N: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        col: usize,
+        key: &[u8],
+        columns: &[(N, V)],
+    ) -> Result<()> {
+        let cf = self.db.as_inner().get_cf(col);
+        assert!(!cf.is_null());
+        let (names, values) = wide_columns::to_slices(columns);
+        moveit! {
+            let status = unsafe {
+                write_batch_put_entity(self.as_inner_mut(), cf, &key.into(), names.as_ptr(), values.as_ptr(), names.len())
+            };
+        }
+        into_result(&status)
+    }
+
     pub fn delete(&mut self, col: usize, key: &[u8]) -> Result<()> {
         let cf = self.db.as_inner().get_cf(col);
         assert!(!cf.is_null());
@@ -39,6 +83,33 @@ impl WriteBatch {
         into_result(&status)
     }
 
+    /// Call `handler` for each entry in the batch, in order. Single deletes
+    /// are reported as deletes. Entries of column families that don't belong
+    /// to the database, e.g. dropped ones, are skipped.
+    pub fn iterate<H: WriteBatchHandler>(&self, handler: &mut H) -> Result<()> {
+        let db = self.db.as_inner();
+        let cols = (0..=self.db.default_col())
+            .filter(|&col| !db.get_cf(col).is_null())
+            .map(|col| (db.get_cf_id(col), col))
+            .collect();
+        let mut ctx = HandlerCtx { handler, cols };
+        moveit! {
+            let status = unsafe {
+                iterate_write_batch(
+                    self.as_inner(),
+                    &mut ctx as *mut HandlerCtx<H> as *mut c_void,
+                    put_fn::<H> as *mut c_void,
+                    delete_fn::<H> as *mut c_void,
+                    delete_range_fn::<H> as *mut c_void,
+                    merge_fn::<H> as *mut c_void,
+                    log_data_fn::<H> as *mut c_void,
+                    put_entity_fn::<H> as *mut c_void,
+                )
+            };
+        }
+        into_result(&status)
+    }
+
     pub fn as_inner_mut(&mut self) -> Pin<&mut autorocks_sys::rocksdb::WriteBatch> {
         match self.inner.as_mut() {
             Some(x) => x,
@@ -50,3 +121,83 @@ impl WriteBatch {
         &self.inner
     }
 }
+
+struct HandlerCtx<'a, H> {
+    handler: &'a mut H,
+    /// Column family id to index.
+    cols: HashMap<u32, usize>,
+}
+
+impl<'a, H> HandlerCtx<'a, H> {
+    /// Safety: ctx must point to a live HandlerCtx<H>.
+    unsafe fn from_raw(ctx: *mut c_void) -> &'a mut Self {
+        &mut *(ctx as *mut Self)
+    }
+}
+
+unsafe extern "C" fn put_fn<H: WriteBatchHandler>(
+    ctx: *mut c_void,
+    cf_id: u32,
+    key: Slice,
+    value: Slice,
+) {
+    let ctx = HandlerCtx::<H>::from_raw(ctx);
+    if let Some(&col) = ctx.cols.get(&cf_id) {
+        ctx.handler
+            .put(col, as_rust_slice1(key), as_rust_slice1(value));
+    }
+}
+
+unsafe extern "C" fn delete_fn<H: WriteBatchHandler>(ctx: *mut c_void, cf_id: u32, key: Slice) {
+    let ctx = HandlerCtx::<H>::from_raw(ctx);
+    if let Some(&col) = ctx.cols.get(&cf_id) {
+        ctx.handler.delete(col, as_rust_slice1(key));
+    }
+}
+
+unsafe extern "C" fn delete_range_fn<H: WriteBatchHandler>(
+    ctx: *mut c_void,
+    cf_id: u32,
+    begin_key: Slice,
+    end_key: Slice,
+) {
+    let ctx = HandlerCtx::<H>::from_raw(ctx);
+    if let Some(&col) = ctx.cols.get(&cf_id) {
+        ctx.handler
+            .delete_range(col, as_rust_slice1(begin_key), as_rust_slice1(end_key));
+    }
+}
+
+unsafe extern "C" fn merge_fn<H: WriteBatchHandler>(
+    ctx: *mut c_void,
+    cf_id: u32,
+    key: Slice,
+    value: Slice,
+) {
+    let ctx = HandlerCtx::<H>::from_raw(ctx);
+    if let Some(&col) = ctx.cols.get(&cf_id) {
+        ctx.handler
+            .merge(col, as_rust_slice1(key), as_rust_slice1(value));
+    }
+}
+
+unsafe extern "C" fn log_data_fn<H: WriteBatchHandler>(ctx: *mut c_void, blob: Slice) {
+    let ctx = HandlerCtx::<H>::from_raw(ctx);
+    ctx.handler.log_data(as_rust_slice1(blob));
+}
+
+unsafe extern "C" fn put_entity_fn<H: WriteBatchHandler>(
+    ctx: *mut c_void,
+    cf_id: u32,
+    key: Slice,
+    columns: *const WideColumnPod,
+    len: usize,
+) {
+    let ctx = HandlerCtx::<H>::from_raw(ctx);
+    if let Some(&col) = ctx.cols.get(&cf_id) {
+        let columns: Vec<_> = (0..len)
+            .map(|i| wide_columns::from_pod(ptr::read(columns.add(i))))
+            .collect();
+        ctx.handler.put_entity(col, as_rust_slice1(key), &columns);
+    }
+}
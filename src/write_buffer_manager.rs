@@ -0,0 +1,72 @@
+use std::{pin::Pin, ptr, sync::Arc};
+
+use autorocks_sys::{new_write_buffer_manager, WriteBufferManagerWrapper};
+use moveit::Emplace;
+
+use crate::Cache;
+
+/// Limits total memtable memory. Can be shared between databases with
+/// `DbOptions::write_buffer_manager`.
+///
+/// Clones refer to the same manager.
+#[derive(Clone)]
+pub struct WriteBufferManager {
+    inner: Arc<WriteBufferManagerWrapper>,
+}
+
+impl WriteBufferManager {
+    /// `buffer_size = 0` means no limit.
+    ///
+    /// If `cache` is provided, memtable memory is charged to the cache (even
+    /// if there is no limit).
+    ///
+    /// If `allow_stall` is true, writes stall when memory usage exceeds
+    /// `buffer_size`, until flushes bring it down.
+    pub fn new(buffer_size: usize, cache: Option<&Cache>, allow_stall: bool) -> Self {
+        let cache = cache.map_or(ptr::null(), |c| c.as_inner() as *const _);
+        let manager =
+            Arc::emplace(unsafe { new_write_buffer_manager(buffer_size, cache, allow_stall) });
+        Self {
+            inner: Pin::into_inner(manager),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.inner.enabled()
+    }
+
+    pub fn cost_to_cache(&self) -> bool {
+        self.inner.cost_to_cache()
+    }
+
+    /// Total memory used by memtables. Only valid if enabled.
+    pub fn memory_usage(&self) -> usize {
+        self.inner.memory_usage()
+    }
+
+    /// Memory used by active (mutable) memtables.
+    pub fn mutable_memtable_memory_usage(&self) -> usize {
+        self.inner.mutable_memtable_memory_usage()
+    }
+
+    /// Memory charged to the cache.
+    pub fn dummy_entries_in_cache_usage(&self) -> usize {
+        self.inner.dummy_entries_in_cache_usage()
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        self.inner.buffer_size()
+    }
+
+    pub fn set_buffer_size(&self, new_size: usize) {
+        self.inner.set_buffer_size(new_size);
+    }
+
+    pub fn is_stall_active(&self) -> bool {
+        self.inner.is_stall_active()
+    }
+
+    pub fn as_inner(&self) -> &WriteBufferManagerWrapper {
+        &self.inner
+    }
+}
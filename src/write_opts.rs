@@ -0,0 +1,100 @@
+use std::pin::Pin;
+
+use autorocks_sys::rocksdb::{Env_IOPriority, TransactionDBWriteOptimizations, WriteOptions};
+use moveit::Emplace;
+
+/// Options for writes. Pass `as_inner()` to methods like
+/// `TransactionDb::put_with_options`, and also `optimizations()` to
+/// `TransactionDb::write_with_options`.
+pub struct WriteOpts {
+    inner: Pin<Box<WriteOptions>>,
+    optimizations: Pin<Box<TransactionDBWriteOptimizations>>,
+}
+
+impl Default for WriteOpts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriteOpts {
+    pub fn new() -> Self {
+        Self {
+            inner: Box::emplace(WriteOptions::new()),
+            optimizations: Box::emplace(TransactionDBWriteOptimizations::new()),
+        }
+    }
+
+    /// Sync the WAL before the write returns. Without this, recent writes
+    /// can be lost if the machine (but not only the process) crashes.
+    pub fn sync(&mut self, val: bool) -> &mut Self {
+        self.inner.sync = val;
+        self
+    }
+
+    /// Skip the WAL. Writes are lost if the process crashes before they are
+    /// flushed.
+    pub fn disable_wal(&mut self, val: bool) -> &mut Self {
+        self.inner.disableWAL = val;
+        self
+    }
+
+    /// Ignore writes to dropped column families in a write batch instead of
+    /// failing.
+    pub fn ignore_missing_column_families(&mut self, val: bool) -> &mut Self {
+        self.inner.ignore_missing_column_families = val;
+        self
+    }
+
+    /// Fail with an `Incomplete` error instead of waiting if the write would
+    /// be delayed or stopped by a write stall.
+    pub fn no_slowdown(&mut self, val: bool) -> &mut Self {
+        self.inner.no_slowdown = val;
+        self
+    }
+
+    /// Slow down this write when compaction falls behind, in favour of
+    /// writes without `low_pri`.
+    pub fn low_pri(&mut self, val: bool) -> &mut Self {
+        self.inner.low_pri = val;
+        self
+    }
+
+    /// Use one memtable insert hint per write batch. Only useful with
+    /// concurrent memtable writes and batches of many sequential keys.
+    pub fn memtable_insert_hint_per_batch(&mut self, val: bool) -> &mut Self {
+        self.inner.memtable_insert_hint_per_batch = val;
+        self
+    }
+
+    /// Charge WAL writes to the rate limiter at this priority. Only
+    /// `Env_IOPriority::IO_USER` is supported. `Env_IOPriority::IO_TOTAL`
+    /// (the default) means they are not charged.
+    pub fn rate_limiter_priority(&mut self, val: Env_IOPriority) -> &mut Self {
+        self.inner.rate_limiter_priority = val;
+        self
+    }
+
+    /// The caller guarantees that the keys written don't conflict with any
+    /// concurrent transaction, so locking is skipped. Part of
+    /// `optimizations()`.
+    pub fn skip_concurrency_control(&mut self, val: bool) -> &mut Self {
+        self.optimizations.skip_concurrency_control = val;
+        self
+    }
+
+    /// The caller guarantees that a write batch contains no duplicate keys.
+    /// Part of `optimizations()`.
+    pub fn skip_duplicate_key_check(&mut self, val: bool) -> &mut Self {
+        self.optimizations.skip_duplicate_key_check = val;
+        self
+    }
+
+    pub fn as_inner(&self) -> &WriteOptions {
+        &self.inner
+    }
+
+    pub fn optimizations(&self) -> &TransactionDBWriteOptimizations {
+        &self.optimizations
+    }
+}
@@ -1,6 +1,19 @@
 use autorocks::*;
-use autorocks_sys::rocksdb::{CompressionType, PinnableSlice, Status_Code};
+use autorocks_sys::rocksdb::{
+    BackgroundErrorReason, BlockBasedTableOptions_IndexType, CompactionReason, CompactionStyle,
+    CompressionType, Env_IOPriority, FlushReason, Histograms, PerfLevel, PinnableSlice,
+    RateLimiter_Mode, StatsLevel, Status_Code, TableFileCreationReason, Tickers,
+    WriteStallCondition,
+};
 use moveit::moveit;
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex,
+    },
+    time::Duration,
+};
 use tempfile::{tempdir, TempDir};
 
 fn open_temp(columns: usize) -> (TransactionDb, TempDir) {
@@ -63,6 +76,536 @@ fn test_read_only_db() {
     assert_eq!(v.unwrap(), b"value");
 }
 
+#[test]
+fn test_properties() {
+    let (db, dir) = open_temp(2);
+    db.put(0, b"key", b"value").unwrap();
+    db.put(1, b"key", b"value").unwrap();
+    assert_eq!(
+        db.get_int_property(0, properties::NUM_ENTRIES_ACTIVE_MEM_TABLE),
+        Some(1)
+    );
+    assert_eq!(
+        db.get_aggregated_int_property(properties::NUM_ENTRIES_ACTIVE_MEM_TABLE),
+        Some(2)
+    );
+    assert!(!db.get_property(0, properties::STATS).unwrap().is_empty());
+    assert!(!db
+        .get_map_property(0, properties::CF_STATS)
+        .unwrap()
+        .is_empty());
+    assert!(db.get_property(0, "rocksdb.no-such-property").is_none());
+    assert!(db.get_map_property(0, "rocksdb.no-such-property").is_none());
+    // Reopen so that the memtables are flushed to SST files.
+    drop(db);
+    drop(DbOptions::new(dir.path(), 2).open().unwrap());
+
+    let rdb = DbOptions::new(dir.path(), 2).open_read_only().unwrap();
+    assert_eq!(
+        rdb.get_property(0, &format!("{}0", properties::NUM_FILES_AT_LEVEL_PREFIX))
+            .unwrap(),
+        "1"
+    );
+    let table_props = rdb
+        .get_map_property(0, properties::AGGREGATED_TABLE_PROPERTIES)
+        .unwrap();
+    assert_eq!(table_props["num_entries"], "1");
+    assert_eq!(
+        rdb.get_aggregated_int_property(properties::TOTAL_SST_FILES_SIZE)
+            .map(|size| size > 0),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_statistics() {
+    let statistics = Statistics::with_stats_level(StatsLevel::kAll);
+    assert!(statistics.stats_level() == StatsLevel::kAll);
+    let dir = tempdir().unwrap();
+    let db = DbOptions::new(dir.path(), 1)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .statistics(&statistics)
+        .open()
+        .unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    db.get(0, b"key", slice.as_mut()).unwrap().unwrap();
+
+    assert_eq!(statistics.ticker(Tickers::NUMBER_KEYS_WRITTEN), 1);
+    assert!(statistics.ticker(Tickers::BYTES_WRITTEN) > 0);
+    assert_eq!(statistics.ticker(Tickers::NUMBER_KEYS_READ), 1);
+    assert_eq!(statistics.ticker(Tickers::MEMTABLE_HIT), 1);
+    let get = statistics.histogram(Histograms::DB_GET);
+    assert_eq!(get.count, 1);
+    assert!(get.max >= get.min);
+    assert!(statistics
+        .to_string()
+        .contains("rocksdb.number.keys.written COUNT : 1"));
+
+    statistics.reset().unwrap();
+    assert_eq!(statistics.ticker(Tickers::NUMBER_KEYS_WRITTEN), 0);
+    assert_eq!(statistics.histogram(Histograms::DB_GET).count, 0);
+}
+
+#[test]
+fn test_perf_context() {
+    let (db, _dir) = open_temp(1);
+    db.put(0, b"key0", b"value").unwrap();
+    {
+        let guard = PerfContext::enable(PerfLevel::kEnableTime);
+        assert_eq!(guard.perf_context(), PerfContext::default());
+        db.put(0, b"key", b"value").unwrap();
+        moveit! {
+            let mut slice = PinnableSlice::new();
+        }
+        db.get(0, b"key", slice.as_mut()).unwrap().unwrap();
+        let perf = guard.perf_context();
+        assert_eq!(perf.get_from_memtable_count, 1);
+        assert!(perf.user_key_comparison_count > 0);
+        assert!(perf.write_wal_time > 0);
+        assert!(guard.iostats_context().bytes_written > 0);
+
+        guard.reset();
+        assert_eq!(guard.perf_context(), PerfContext::default());
+        assert_eq!(guard.iostats_context(), IOStatsContext::default());
+    }
+    assert!(autorocks_sys::get_perf_level() == PerfLevel::kEnableCount);
+
+    // Can be enabled again once the guard is gone.
+    let guard = PerfContext::enable(PerfLevel::kEnableCount);
+    assert_eq!(guard.perf_context(), PerfContext::default());
+}
+
+#[test]
+#[should_panic(expected = "perf context is already enabled on this thread")]
+fn test_perf_context_nested() {
+    let _guard = PerfContext::enable(PerfLevel::kEnableCount);
+    let _ = PerfContext::enable(PerfLevel::kEnableCount);
+}
+
+// Sends events to the channels of a `JobEvents`.
+struct JobListener {
+    flushes: Mutex<mpsc::Sender<FlushJobInfo>>,
+    compactions: Mutex<mpsc::Sender<CompactionJobInfo>>,
+    stalls: Mutex<mpsc::Sender<WriteStallInfo>>,
+    background_errors: Mutex<mpsc::Sender<(BackgroundErrorReason, RocksDBStatusError)>>,
+    table_files: Mutex<mpsc::Sender<TableFileCreationInfo>>,
+}
+
+struct JobEvents {
+    flushes: mpsc::Receiver<FlushJobInfo>,
+    compactions: mpsc::Receiver<CompactionJobInfo>,
+    stalls: mpsc::Receiver<WriteStallInfo>,
+    background_errors: mpsc::Receiver<(BackgroundErrorReason, RocksDBStatusError)>,
+    table_files: mpsc::Receiver<TableFileCreationInfo>,
+}
+
+fn job_listener() -> (JobListener, JobEvents) {
+    let (flushes, flush_receiver) = mpsc::channel();
+    let (compactions, compaction_receiver) = mpsc::channel();
+    let (stalls, stall_receiver) = mpsc::channel();
+    let (background_errors, background_error_receiver) = mpsc::channel();
+    let (table_files, table_file_receiver) = mpsc::channel();
+    (
+        JobListener {
+            flushes: Mutex::new(flushes),
+            compactions: Mutex::new(compactions),
+            stalls: Mutex::new(stalls),
+            background_errors: Mutex::new(background_errors),
+            table_files: Mutex::new(table_files),
+        },
+        JobEvents {
+            flushes: flush_receiver,
+            compactions: compaction_receiver,
+            stalls: stall_receiver,
+            background_errors: background_error_receiver,
+            table_files: table_file_receiver,
+        },
+    )
+}
+
+// Send errors are ignored: the receivers may be gone while the database is
+// still running background jobs.
+impl EventListener for JobListener {
+    fn on_flush_completed(&self, info: FlushJobInfo) {
+        let _ = self.flushes.lock().unwrap().send(info);
+    }
+
+    fn on_compaction_completed(&self, info: CompactionJobInfo) {
+        let _ = self.compactions.lock().unwrap().send(info);
+    }
+
+    fn on_stall_conditions_changed(&self, info: WriteStallInfo) {
+        let _ = self.stalls.lock().unwrap().send(info);
+    }
+
+    fn on_background_error(&self, reason: BackgroundErrorReason, error: RocksDBStatusError) {
+        let _ = self.background_errors.lock().unwrap().send((reason, error));
+    }
+
+    fn on_table_file_created(&self, info: TableFileCreationInfo) {
+        let _ = self.table_files.lock().unwrap().send(info);
+    }
+}
+
+#[test]
+fn test_event_listener() {
+    let (listener, events) = job_listener();
+    let dir = tempdir().unwrap();
+    let db = DbOptions::new(dir.path(), 1)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .add_event_listener(listener)
+        .open()
+        .unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    // Creating a checkpoint flushes the memtables.
+    let checkpoint_dir = tempdir().unwrap();
+    db.create_checkpoint(&checkpoint_dir.path().join("checkpoint"), 0)
+        .unwrap();
+    let info = events
+        .flushes
+        .recv_timeout(Duration::from_secs(10))
+        .unwrap();
+    assert_eq!(info.cf_name, "0");
+    assert!(info.file_path.starts_with(dir.path()));
+    assert!(info.flush_reason == FlushReason::kGetLiveFiles);
+    assert_eq!(info.table_properties.num_entries, 1);
+    assert!(format!("{:?}", info).contains("cf_name: \"0\""));
+
+    let table_file = events
+        .table_files
+        .recv_timeout(Duration::from_secs(10))
+        .unwrap();
+    table_file.status.unwrap();
+    assert_eq!(table_file.cf_name, "0");
+    assert_eq!(table_file.file_path, info.file_path);
+    assert!(table_file.reason == TableFileCreationReason::kFlush);
+    assert!(table_file.file_size > 0);
+    assert_eq!(table_file.table_properties.num_entries, 1);
+
+    // The overwritten key is flushed to a second, overlapping L0 file, so
+    // both files are compacted rather than moved.
+    db.put(0, b"key", b"value1").unwrap();
+    db.compact_range(0, None, None).unwrap();
+    let info = events
+        .compactions
+        .recv_timeout(Duration::from_secs(10))
+        .unwrap();
+    assert_eq!(info.cf_name, "0");
+    info.status.unwrap();
+    assert!(info.compaction_reason == CompactionReason::kManualCompaction);
+    assert_eq!(info.input_files.len(), 2);
+    assert_eq!(info.num_input_records, 2);
+    assert_eq!(info.num_output_records, 1);
+    assert!(info
+        .output_files
+        .iter()
+        .all(|path| path.starts_with(dir.path())));
+}
+
+#[test]
+fn test_event_listener_write_stall() {
+    let (listener, events) = job_listener();
+    let dir = tempdir().unwrap();
+    let mut cf_options = CfOptions::new();
+    cf_options
+        .level0_file_num_compaction_trigger(1)
+        .level0_slowdown_writes_trigger(1);
+    let db = DbOptions::new(dir.path(), 1)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .cf_options(0, &cf_options)
+        .add_event_listener(listener)
+        .open()
+        .unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    // A single L0 file slows down writes until it is compacted.
+    let checkpoint_dir = tempdir().unwrap();
+    db.create_checkpoint(&checkpoint_dir.path().join("checkpoint"), 0)
+        .unwrap();
+    let info = events.stalls.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(info.cf_name, "0");
+    assert!(info.cur == WriteStallCondition::kDelayed);
+    assert!(info.prev == WriteStallCondition::kNormal);
+    let info = events.stalls.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert!(info.cur == WriteStallCondition::kNormal);
+    assert!(info.prev == WriteStallCondition::kDelayed);
+}
+
+#[test]
+fn test_event_listener_background_error() {
+    let (listener, events) = job_listener();
+    let fs = FaultInjectionFs::new(&Env::new_mem());
+    let db = DbOptions::new(Path::new("/autorocks-background-error-test"), 1)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .env(&fs.env())
+        .add_event_listener(listener)
+        .open()
+        .unwrap();
+    fs.set_fail_writes(true);
+    assert!(db.put(0, b"key", b"value").is_err());
+    let (reason, error) = events
+        .background_errors
+        .recv_timeout(Duration::from_secs(10))
+        .unwrap();
+    assert!(reason == BackgroundErrorReason::kWriteCallback);
+    assert!(error.code == Status_Code::kIOError);
+}
+
+#[test]
+fn test_resume_without_background_error() {
+    let (db, _dir) = open_temp(1);
+    db.put(0, b"key", b"value").unwrap();
+    assert!(db.background_error().is_none());
+    db.resume().unwrap();
+    assert!(db.background_error().is_none());
+    db.put(0, b"key1", b"value1").unwrap();
+}
+
+#[derive(Default)]
+struct CollectingHandler {
+    entries: Vec<(usize, Vec<u8>, Option<Vec<u8>>)>,
+}
+
+impl WriteBatchHandler for CollectingHandler {
+    fn put(&mut self, col: usize, key: &[u8], value: &[u8]) {
+        self.entries.push((col, key.to_vec(), Some(value.to_vec())));
+    }
+
+    fn delete(&mut self, col: usize, key: &[u8]) {
+        self.entries.push((col, key.to_vec(), None));
+    }
+
+    fn delete_range(&mut self, _col: usize, _begin_key: &[u8], _end_key: &[u8]) {}
+}
+
+#[test]
+fn test_get_updates_since() {
+    let dir = tempdir().unwrap();
+    let db = DbOptions::new(dir.path(), 2)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .wal_ttl(Duration::from_secs(3600))
+        .wal_size_limit_mb(64)
+        .open()
+        .unwrap();
+    db.put(0, b"a", b"1").unwrap();
+    let mut batch = db.new_write_batch();
+    batch.put(1, b"b", b"2").unwrap();
+    batch.delete(0, b"a").unwrap();
+    db.write(&mut batch).unwrap();
+    assert_eq!(db.latest_sequence_number(), 3);
+
+    let mut handler = CollectingHandler::default();
+    let mut sequence_numbers = vec![];
+    for update in db.get_updates_since(1).unwrap() {
+        let (sequence_number, batch) = update.unwrap();
+        sequence_numbers.push(sequence_number);
+        batch.iterate(&mut handler).unwrap();
+    }
+    assert_eq!(sequence_numbers, [1, 2]);
+    assert_eq!(
+        handler.entries,
+        [
+            (0, b"a".to_vec(), Some(b"1".to_vec())),
+            (1, b"b".to_vec(), Some(b"2".to_vec())),
+            (0, b"a".to_vec(), None),
+        ]
+    );
+
+    let updates: Vec<_> = db.get_updates_since(2).unwrap().collect();
+    assert_eq!(updates.len(), 1);
+}
+
+#[test]
+fn test_write_opts() {
+    let (db, _dir) = open_temp(1);
+    let mut options = WriteOpts::new();
+    options.sync(true);
+    db.put_with_options(options.as_inner(), 0, b"a", b"1")
+        .unwrap();
+    options.sync(false).disable_wal(true);
+    db.put_with_options(options.as_inner(), 0, b"b", b"2")
+        .unwrap();
+    let mut batch = db.new_write_batch();
+    batch.put(0, b"c", b"3").unwrap();
+    options
+        .skip_concurrency_control(true)
+        .skip_duplicate_key_check(true);
+    db.write_with_options(options.as_inner(), options.optimizations(), &mut batch)
+        .unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    for key in [b"a", b"b", b"c"] {
+        assert!(db.get(0, key, slice.as_mut()).unwrap().is_some());
+    }
+    // Only the first write went to the WAL.
+    assert_eq!(db.get_updates_since(1).unwrap().count(), 1);
+}
+
+#[test]
+fn test_write_opts_no_slowdown() {
+    let dir = tempdir().unwrap();
+    let manager = WriteBufferManager::new(1, None, true);
+    let db = DbOptions::new(dir.path(), 1)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .write_buffer_manager(&manager)
+        .open()
+        .unwrap();
+    let mut options = WriteOpts::new();
+    options.no_slowdown(true);
+    let value = vec![0; 64 << 10];
+    let err = (0..2u8)
+        .find_map(|i| {
+            db.put_with_options(options.as_inner(), 0, &[i], &value)
+                .err()
+        })
+        .unwrap();
+    assert!(err.is_incomplete());
+}
+
+#[test]
+fn test_read_opts_deadline() {
+    let dir = tempdir().unwrap();
+    let mut table_options = BlockBasedOptions::new();
+    table_options.no_block_cache(true);
+    let mut cf_options = CfOptions::new();
+    cf_options.block_based_table_factory(&table_options);
+    let mut options = DbOptions::new(dir.path(), 1);
+    options
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .cf_options(0, &cf_options);
+    let db = options.open().unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    drop(db);
+    // Recovery writes the WAL to a table file, so reads below go to disk.
+    let db = options.open().unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    let mut read_options = ReadOpts::new();
+    read_options.io_timeout(Duration::from_secs(10));
+    let v = db
+        .get_with_options(read_options.as_inner(), 0, b"key", slice.as_mut())
+        .unwrap();
+    assert_eq!(v.unwrap(), b"value");
+
+    read_options.deadline(Duration::ZERO);
+    let err = db
+        .get_with_options(read_options.as_inner(), 0, b"key", slice.as_mut())
+        .unwrap_err();
+    assert!(err.is_timed_out());
+    let mut iter = db.iter_with_options(read_options.as_inner(), 0, Direction::Forward);
+    assert!(iter.next().is_none());
+    assert!(iter.status().unwrap_err().is_timed_out());
+
+    // The deadline is fixed when set, so reused options go stale.
+    read_options.deadline(Duration::from_millis(1));
+    std::thread::sleep(Duration::from_millis(10));
+    let err = db
+        .get_with_options(read_options.as_inner(), 0, b"key", slice.as_mut())
+        .unwrap_err();
+    assert!(err.is_timed_out());
+    read_options.deadline(Duration::from_secs(60));
+    let v = db
+        .get_with_options(read_options.as_inner(), 0, b"key", slice.as_mut())
+        .unwrap();
+    assert_eq!(v.unwrap(), b"value");
+}
+
+#[cfg(feature = "async")]
+fn block_on<F: std::future::Future>(f: F) -> F::Output {
+    use std::{
+        sync::Arc,
+        task::{Context, Poll, Wake},
+        thread::Thread,
+    };
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut f = Box::pin(f);
+    loop {
+        match f.as_mut().poll(&mut cx) {
+            Poll::Ready(v) => return v,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_db() {
+    let (db, _dir) = open_temp(2);
+    let db = AsyncDb::new(db, 2);
+    block_on(async {
+        db.put(0, b"a", b"1").await.unwrap();
+        let mut batch = db.db().new_write_batch();
+        batch.put(1, b"b", b"2").unwrap();
+        db.write(batch).await.unwrap();
+        let mut transaction = db.db().begin_transaction();
+        transaction.put(0, b"c", b"3").unwrap();
+        db.commit(transaction).await.unwrap();
+
+        assert_eq!(db.get(0, b"a").await.unwrap(), Some(b"1".to_vec()));
+        assert_eq!(
+            db.multi_get(0, vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()])
+                .await
+                .unwrap(),
+            [Some(b"1".to_vec()), None, Some(b"3".to_vec())]
+        );
+        db.delete(0, b"a").await.unwrap();
+        assert_eq!(db.get(0, b"a").await.unwrap(), None);
+    });
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_db_backpressure() {
+    let (db, _dir) = open_temp(1);
+    let db = AsyncDb::with_queue_capacity(db, 1, 1);
+    let mut transaction = db.db().begin_transaction();
+    transaction.put(0, b"a", b"1").unwrap();
+    // The thread waits for the lock held by the transaction, so the queue
+    // fills up and the last put waits for room.
+    let first = db.put(0, b"a", b"2");
+    let second = db.put(0, b"b", b"2");
+    let mut third = db.put(0, b"c", b"3");
+    let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    assert!(std::future::Future::poll(std::pin::Pin::new(&mut third), &mut cx).is_pending());
+    transaction.rollback().unwrap();
+    block_on(third).unwrap();
+    block_on(first).unwrap();
+    block_on(second).unwrap();
+    assert_eq!(block_on(db.get(0, b"c")).unwrap(), Some(b"3".to_vec()));
+}
+
+#[cfg(feature = "async")]
+#[test]
+#[should_panic(expected = "AsyncTask polled after completion")]
+fn test_async_task_poll_after_completion() {
+    let (db, _dir) = open_temp(1);
+    let db = AsyncDb::new(db, 1);
+    let mut task = db.get(0, b"a");
+    assert_eq!(block_on(&mut task).unwrap(), None);
+    let _ = block_on(&mut task);
+}
+
 #[cfg(feature = "snappy")]
 #[test]
 fn test_db_open_snappy() {
@@ -76,6 +619,562 @@ fn test_db_open_snappy() {
     db.put(0, b"key", b"value").unwrap();
 }
 
+#[test]
+fn test_cf_options() {
+    let dir = tempdir().unwrap();
+    let mut table_options = BlockBasedOptions::new();
+    table_options
+        .block_size(16 * 1024)
+        .ribbon_filter(10.0, 1)
+        .cache_index_and_filter_blocks(true)
+        .index_type(BlockBasedTableOptions_IndexType::kTwoLevelIndexSearch)
+        .partition_filters(true);
+    let mut cf_options = CfOptions::new();
+    cf_options
+        .write_buffer_size(1 << 20)
+        .max_write_buffer_number(3)
+        .target_file_size_base(8 << 20)
+        .level0_file_num_compaction_trigger(2)
+        .level0_slowdown_writes_trigger(10)
+        .level0_stop_writes_trigger(20)
+        .compaction_style(CompactionStyle::kCompactionStyleLevel)
+        .compression_per_level(&[
+            CompressionType::kNoCompression,
+            CompressionType::kNoCompression,
+        ])
+        .block_based_table_factory(&table_options);
+    let db = DbOptions::new(dir.path(), 1)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .cf_options(0, &cf_options)
+        .open()
+        .unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    let v = db.get(0, b"key", slice.as_mut()).unwrap();
+    assert_eq!(v.unwrap(), b"value");
+
+    let options = latest_options_file(dir.path());
+    assert!(options.contains("write_buffer_size=1048576"));
+    assert!(options.contains("index_type=kTwoLevelIndexSearch"));
+    assert!(options.contains("partition_filters=true"));
+}
+
+/// The contents of the newest OPTIONS file of the database in `dir`.
+fn latest_options_file(dir: &Path) -> String {
+    let options_file = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| {
+            p.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("OPTIONS-")
+        })
+        .max()
+        .unwrap();
+    std::fs::read_to_string(options_file).unwrap()
+}
+
+#[test]
+fn test_shared_cache() {
+    let cache = Cache::new_lru(8 << 20, -1, false, 0.5);
+    let mut table_options = BlockBasedOptions::new();
+    table_options.block_cache(&cache);
+    let mut cf_options = CfOptions::new();
+    cf_options.block_based_table_factory(&table_options);
+
+    let dir = tempdir().unwrap();
+    let dir1 = tempdir().unwrap();
+    let open = |dir: &TempDir| {
+        DbOptions::new(dir.path(), 1)
+            .create_if_missing(true)
+            .create_missing_column_families(true)
+            .cf_options(0, &cf_options)
+            .open()
+            .unwrap()
+    };
+    let db = open(&dir);
+    let db1 = open(&dir1);
+    db.put(0, b"key", b"value").unwrap();
+    db1.put(0, b"key", b"value1").unwrap();
+    // Reopen so that the memtables are flushed to SST files.
+    drop((db, db1));
+    let db = open(&dir);
+    let db1 = open(&dir1);
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert_eq!(
+        db.get(0, b"key", slice.as_mut()).unwrap().unwrap(),
+        b"value"
+    );
+    assert_eq!(
+        db1.get(0, b"key", slice.as_mut()).unwrap().unwrap(),
+        b"value1"
+    );
+    assert!(cache.usage() > 0);
+    assert!(cache.pinned_usage() <= cache.usage());
+
+    cache.set_capacity(1 << 20);
+    assert_eq!(cache.capacity(), 1 << 20);
+
+    let cache = Cache::new_hyper_clock(8 << 20, 4096, -1, true);
+    assert_eq!(cache.capacity(), 8 << 20);
+    assert_eq!(cache.usage(), 0);
+}
+
+#[test]
+fn test_write_buffer_manager() {
+    let cache = Cache::new_lru(64 << 20, -1, false, 0.5);
+    let manager = WriteBufferManager::new(32 << 20, Some(&cache), true);
+    assert!(manager.enabled());
+    assert!(manager.cost_to_cache());
+    assert_eq!(manager.buffer_size(), 32 << 20);
+
+    let dir = tempdir().unwrap();
+    let dir1 = tempdir().unwrap();
+    let open = |dir: &TempDir| {
+        DbOptions::new(dir.path(), 1)
+            .create_if_missing(true)
+            .create_missing_column_families(true)
+            .write_buffer_manager(&manager)
+            .open()
+            .unwrap()
+    };
+    let db = open(&dir);
+    let db1 = open(&dir1);
+    let usage = manager.memory_usage();
+    for i in 0..1000u32 {
+        db.put(0, &i.to_be_bytes(), &[0; 100]).unwrap();
+        db1.put(0, &i.to_be_bytes(), &[0; 100]).unwrap();
+    }
+    assert!(manager.memory_usage() > usage);
+    assert!(manager.mutable_memtable_memory_usage() > 0);
+    assert!(manager.dummy_entries_in_cache_usage() > 0);
+    assert!(cache.usage() >= manager.dummy_entries_in_cache_usage());
+    assert!(!manager.is_stall_active());
+
+    manager.set_buffer_size(64 << 20);
+    assert_eq!(manager.buffer_size(), 64 << 20);
+}
+
+#[test]
+fn test_rate_limiter() {
+    let limiter = RateLimiter::with_options(
+        16 << 20,
+        Duration::from_millis(10),
+        10,
+        RateLimiter_Mode::kWritesOnly,
+        false,
+    );
+    assert_eq!(limiter.bytes_per_second(), 16 << 20);
+    assert!(limiter.single_burst_bytes() > 0);
+
+    let dir = tempdir().unwrap();
+    let dir1 = tempdir().unwrap();
+    let open = |dir: &TempDir| {
+        DbOptions::new(dir.path(), 1)
+            .create_if_missing(true)
+            .create_missing_column_families(true)
+            .rate_limiter(&limiter)
+            .open()
+            .unwrap()
+    };
+    let db = open(&dir);
+    let db1 = open(&dir1);
+    let mut options = WriteOpts::new();
+    options.rate_limiter_priority(Env_IOPriority::IO_USER);
+    db.put_with_options(options.as_inner(), 0, b"key", &[0; 100])
+        .unwrap();
+    db1.put_with_options(options.as_inner(), 0, b"key", &[0; 100])
+        .unwrap();
+    assert!(limiter.total_bytes_through(Env_IOPriority::IO_USER) >= 200);
+    assert!(limiter.total_requests(Env_IOPriority::IO_TOTAL) >= 2);
+
+    limiter.set_bytes_per_second(1 << 20);
+    assert_eq!(limiter.bytes_per_second(), 1 << 20);
+}
+
+#[test]
+fn test_sst_file_manager() {
+    let manager = SstFileManager::new();
+    manager.set_delete_rate_bytes_per_sec(1 << 20);
+    assert_eq!(manager.delete_rate_bytes_per_sec(), 1 << 20);
+    manager.set_compaction_buffer_size(1 << 20);
+
+    let dir = tempdir().unwrap();
+    let mut cf_options = CfOptions::new();
+    cf_options.write_buffer_size(64 << 10);
+    let mut options = DbOptions::new(dir.path(), 1);
+    options
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .cf_options(0, &cf_options)
+        .sst_file_manager(&manager);
+    let db = options.open().unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    drop(db);
+    // Recovery writes the WAL to a table file.
+    let db = options.open().unwrap();
+    let files = manager.tracked_files();
+    assert!(!files.is_empty());
+    assert!(files.keys().all(|path| path.starts_with(dir.path())));
+    assert_eq!(files.values().sum::<u64>(), manager.total_size());
+    assert!(!manager.is_max_allowed_space_reached());
+
+    manager.set_max_allowed_space_usage(1);
+    assert!(manager.is_max_allowed_space_reached());
+    // Creating a checkpoint flushes the memtables and waits for the flush,
+    // which hits the limit and stops writes.
+    db.put(0, b"key1", b"value1").unwrap();
+    let checkpoint_dir = tempdir().unwrap();
+    let err = db
+        .create_checkpoint(&checkpoint_dir.path().join("checkpoint"), 0)
+        .unwrap_err();
+    assert!(err.is_no_space());
+    assert!(db.background_error().unwrap().is_no_space());
+    assert!(db.put(0, b"key2", b"value2").unwrap_err().is_no_space());
+}
+
+#[test]
+fn test_prefix_extractor() {
+    struct UntilColon;
+
+    impl CustomSliceTransform for UntilColon {
+        fn name(&self) -> &str {
+            "UntilColon"
+        }
+
+        fn transform<'a>(&self, key: &'a [u8]) -> &'a [u8] {
+            let end = key.iter().position(|b| *b == b':').unwrap();
+            &key[..end]
+        }
+
+        fn in_domain(&self, key: &[u8]) -> bool {
+            key.contains(&b':')
+        }
+    }
+
+    let mut table_options = BlockBasedOptions::new();
+    table_options.bloom_filter(10.0).whole_key_filtering(false);
+    let mut fixed = CfOptions::new();
+    fixed
+        .prefix_extractor(&SliceTransform::fixed_prefix(4))
+        .memtable_prefix_bloom_size_ratio(0.1)
+        .memtable_whole_key_filtering(true)
+        .block_based_table_factory(&table_options);
+    let mut custom = CfOptions::new();
+    custom
+        .prefix_extractor(&SliceTransform::custom(UntilColon))
+        .memtable_prefix_bloom_size_ratio(0.1)
+        .block_based_table_factory(&table_options);
+
+    let dir = tempdir().unwrap();
+    let open = || {
+        DbOptions::new(dir.path(), 2)
+            .create_if_missing(true)
+            .create_missing_column_families(true)
+            .cf_options(0, &fixed)
+            .cf_options(1, &custom)
+            .open()
+            .unwrap()
+    };
+    let db = open();
+    for key in [&b"aaaa1"[..], b"aaaa2", b"aaab1", b"bbbb1"] {
+        db.put(0, key, b"value").unwrap();
+    }
+    for key in [&b"a:1"[..], b"a:2", b"aa:1", b"b:1"] {
+        db.put(1, key, b"value").unwrap();
+    }
+    let check = |db: &TransactionDb| {
+        assert_eq!(db.prefix_iter(0, b"aaaa").count(), 2);
+        assert_eq!(db.prefix_iter(0, b"aaab").count(), 1);
+        assert_eq!(db.prefix_iter(0, b"cccc").count(), 0);
+        assert_eq!(db.prefix_iter(1, b"a:").count(), 2);
+        assert_eq!(db.prefix_iter(1, b"aa:").count(), 1);
+        assert_eq!(db.prefix_iter(1, b"c:").count(), 0);
+    };
+    check(&db);
+    // Reopen so that the memtables are flushed to SST files.
+    drop(db);
+    check(&open());
+}
+
+#[test]
+fn test_cf_ttl() {
+    let dir = tempdir().unwrap();
+    let mut cf_options = CfOptions::new();
+    cf_options
+        // Rounded up to an hour.
+        .ttl(Duration::from_millis(3_599_500))
+        .periodic_compaction(Duration::from_secs(7200));
+    let db = DbOptions::new(dir.path(), 1)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .cf_options(0, &cf_options)
+        .open()
+        .unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    let has_line = |options: &str, line: &str| options.lines().any(|l| l.trim() == line);
+    let options = latest_options_file(dir.path());
+    assert!(has_line(&options, "ttl=3600"));
+    assert!(has_line(&options, "periodic_compaction_seconds=7200"));
+
+    db.set_options(0, [("ttl", "36000")]).unwrap();
+    let options = latest_options_file(dir.path());
+    assert!(has_line(&options, "ttl=36000"));
+    assert!(has_line(&options, "periodic_compaction_seconds=7200"));
+}
+
+#[test]
+#[should_panic(expected = "ttl must not be zero")]
+fn test_ttl_db_zero_ttl() {
+    let dir = tempdir().unwrap();
+    let _ = DbOptions::new(dir.path(), 1)
+        .create_if_missing(true)
+        .open_with_ttl(&[Some(Duration::ZERO)]);
+}
+
+#[test]
+fn test_ttl_db() {
+    let dir = tempdir().unwrap();
+    let db = DbOptions::new(dir.path(), 2)
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .open_with_ttl(&[Some(Duration::from_secs(60))])
+        .unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    db.put(1, b"key1", b"value1").unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert_eq!(
+        db.get(0, b"key", slice.as_mut()).unwrap().unwrap(),
+        b"value"
+    );
+    assert_eq!(
+        db.get(1, b"key1", slice.as_mut()).unwrap().unwrap(),
+        b"value1"
+    );
+    let entries: Vec<_> = db.iter(0, Direction::Forward).collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(&*entries[0].0, b"key");
+    assert_eq!(&*entries[0].1, b"value");
+    db.set_ttl(1, Some(Duration::from_secs(3600)));
+    db.delete(0, b"key").unwrap();
+    assert!(db.get(0, b"key", slice.as_mut()).unwrap().is_none());
+}
+
+#[test]
+fn test_checkpoint_export_import() {
+    let (db, _dir) = open_temp(2);
+    db.put(0, b"key", b"value").unwrap();
+    db.put(1, b"key1", b"value1").unwrap();
+    let tmp = tempdir().unwrap();
+    let checkpoint_dir = tmp.path().join("checkpoint");
+    assert!(db.create_checkpoint(&checkpoint_dir, 0).unwrap() > 0);
+    let checkpoint = DbOptions::new(&checkpoint_dir, 2).open_read_only().unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert_eq!(
+        checkpoint.get(1, b"key1", slice.as_mut()).unwrap().unwrap(),
+        b"value1"
+    );
+
+    let metadata = db
+        .export_column_family(0, &tmp.path().join("export"))
+        .unwrap();
+    assert_eq!(metadata.files.len(), 1);
+    assert_eq!(&*metadata.files[0].smallest_key, b"key");
+    assert_eq!(metadata.files[0].num_entries, 1);
+
+    let dir1 = tempdir().unwrap();
+    let mut options = DbOptions::new(dir1.path(), 2);
+    options
+        .create_if_missing(true)
+        .create_missing_column_families(true);
+    options
+        .create_column_family_with_import(0, &metadata, false)
+        .unwrap();
+    let db1 = options.open().unwrap();
+    assert_eq!(
+        db1.get(0, b"key", slice.as_mut()).unwrap().unwrap(),
+        b"value"
+    );
+    assert!(db1.get(1, b"key1", slice.as_mut()).unwrap().is_none());
+}
+
+#[test]
+fn test_backup_engine() {
+    let (db, _dir) = open_temp(1);
+    db.put(0, b"key", b"value").unwrap();
+    let backup_dir = tempdir().unwrap();
+    let mut engine = BackupEngine::open(backup_dir.path()).unwrap();
+    let id = engine.create_new_backup(&db, true).unwrap();
+    db.put(0, b"key", b"value1").unwrap();
+    // Progress is reported every 4MB copied, here of the unflushed WAL.
+    db.put(0, b"large", &vec![0; 5 << 20]).unwrap();
+    let progress = AtomicUsize::new(0);
+    let id1 = engine
+        .create_new_backup_with_progress(&db, false, || {
+            progress.fetch_add(1, Ordering::Relaxed);
+        })
+        .unwrap();
+    assert!(id1 > id);
+    assert!(progress.load(Ordering::Relaxed) > 0);
+
+    let infos = engine.get_backup_info();
+    assert_eq!(
+        infos.iter().map(|i| i.backup_id).collect::<Vec<_>>(),
+        [id, id1]
+    );
+    assert!(infos[0].number_files > 0);
+    engine.verify_backup(id, true).unwrap();
+
+    engine.purge_old_backups(1).unwrap();
+    assert_eq!(engine.get_backup_info().len(), 1);
+    assert!(engine.verify_backup(id, false).is_err());
+
+    let restore_dir = tempdir().unwrap();
+    engine
+        .restore_db_from_backup(id1, restore_dir.path(), restore_dir.path(), false)
+        .unwrap();
+    let restored = DbOptions::new(restore_dir.path(), 1).open().unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert_eq!(
+        restored.get(0, b"key", slice.as_mut()).unwrap().unwrap(),
+        b"value1"
+    );
+
+    engine.delete_backup(id1).unwrap();
+    assert!(engine.get_backup_info().is_empty());
+}
+
+#[test]
+fn test_sst_file_writer_ingest() {
+    let (db, _dir) = open_temp(1);
+    db.put(0, b"key0", b"old").unwrap();
+    db.put(0, b"key1", b"old").unwrap();
+
+    let sst_dir = tempdir().unwrap();
+    let path = sst_dir.path().join("1.sst");
+    let mut writer = SstFileWriter::open(&CfOptions::new(), &path).unwrap();
+    writer.put(b"key1", b"value1").unwrap();
+    writer.put(b"key2", b"value2").unwrap();
+    writer.delete(b"key3").unwrap();
+    assert!(writer.put(b"key0", b"value0").is_err());
+    writer.delete_range(b"key0", b"key1").unwrap();
+    let info = writer.finish().unwrap();
+    assert_eq!(info.file_path, path);
+    assert_eq!(&*info.smallest_key, b"key1");
+    assert_eq!(&*info.largest_key, b"key3");
+    assert_eq!(info.num_entries, 3);
+    assert_eq!(info.num_range_del_entries, 1);
+    assert!(info.file_size > 0);
+
+    db.ingest_external_files(0, [&path], IngestOpts::new().move_files(true))
+        .unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert!(db.get(0, b"key0", slice.as_mut()).unwrap().is_none());
+    assert_eq!(
+        db.get(0, b"key1", slice.as_mut()).unwrap().unwrap(),
+        b"value1"
+    );
+    assert_eq!(
+        db.get(0, b"key2", slice.as_mut()).unwrap().unwrap(),
+        b"value2"
+    );
+
+    let path = sst_dir.path().join("2.sst");
+    let mut writer = SstFileWriter::open(&CfOptions::new(), &path).unwrap();
+    writer.put(b"key2", b"new").unwrap();
+    writer.finish().unwrap();
+    assert!(db
+        .ingest_external_files(0, [&path], IngestOpts::new().allow_global_seqno(false))
+        .is_err());
+}
+
+#[test]
+fn test_sst_file_reader() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("1.sst");
+    let mut options = CfOptions::new();
+    options.compression(CompressionType::kNoCompression);
+    let mut writer = SstFileWriter::open(&options, &path).unwrap();
+    writer.put(b"key1", b"value1").unwrap();
+    writer.put(b"key2", b"value2").unwrap();
+    writer.finish().unwrap();
+
+    let reader = SstFileReader::open(&options, &path).unwrap();
+    reader.verify_checksum().unwrap();
+    assert_eq!(
+        reader.iter(Direction::Forward).collect::<Vec<_>>(),
+        [
+            (b"key1"[..].into(), b"value1"[..].into()),
+            (b"key2"[..].into(), b"value2"[..].into()),
+        ]
+    );
+    let mut iter = reader.iter(Direction::Backward);
+    iter.seek_for_prev(b"key1");
+    assert_eq!(iter.count(), 1);
+
+    let props = reader.table_properties();
+    assert_eq!(props.num_entries, 2);
+    assert!(props.data_size > 0);
+    // Internal keys have an 8 byte suffix.
+    assert_eq!(props.raw_key_size, 2 * (4 + 8));
+    assert_eq!(props.raw_value_size, 12);
+    assert_eq!(props.compression_name, "NoCompression");
+    assert_eq!(props.comparator_name, "leveldb.BytewiseComparator");
+    assert!(props.file_creation_time > 0);
+
+    assert!(SstFileReader::open(&options, &dir.path().join("missing.sst")).is_err());
+}
+
+#[test]
+fn test_column_family_metadata() {
+    let (db, dir) = open_temp(2);
+    db.put(0, b"key1", b"value1").unwrap();
+    db.put(0, b"key2", b"value2").unwrap();
+    db.put(1, b"key", b"value").unwrap();
+    // Reopen so that the memtables are flushed to SST files.
+    drop(db);
+    let db = DbOptions::new(dir.path(), 2).open().unwrap();
+
+    let metadata = db.column_family_metadata(0);
+    assert_eq!(metadata.name, "0");
+    assert_eq!(metadata.file_count(), 1);
+    assert_eq!(metadata.levels[0].level, 0);
+    let file = &metadata.levels[0].files[0];
+    assert_eq!(metadata.size, file.size);
+    assert_eq!(metadata.levels[0].size, file.size);
+    assert_eq!(file.column_family_name, "0");
+    assert_eq!(&*file.smallest_key, b"key1");
+    assert_eq!(&*file.largest_key, b"key2");
+    assert_eq!(file.num_entries, 2);
+    assert!(file.smallest_seqno <= file.largest_seqno);
+
+    let live_files = db.live_files_metadata();
+    assert_eq!(live_files.len(), 2);
+    assert!(live_files.contains(file));
+    assert!(live_files.iter().any(|f| f.column_family_name == "1"));
+
+    let props = db.get_properties_of_all_tables(0).unwrap();
+    assert_eq!(props.len(), 1);
+    let (path, props) = props.into_iter().next().unwrap();
+    assert!(path.ends_with(&file.relative_filename));
+    assert_eq!(props.num_entries, 2);
+    assert_eq!(props.column_family_name, "0");
+}
+
 #[test]
 fn test_snapshot() {
     let (db, _dir) = open_temp(1);
@@ -172,3 +1271,289 @@ fn test_clear_cf() {
     db.put(0, b"key", b"value").unwrap();
     assert_eq!(db.iter(0, Direction::Forward).count(), 1);
 }
+
+#[test]
+fn test_mem_env() {
+    let path = std::env::temp_dir().join("autorocks-mem-env-test");
+    let env = Env::new_mem();
+    let manager = SstFileManager::with_env(&env);
+    let mut options = DbOptions::new(&path, 1);
+    options
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .env(&env)
+        .sst_file_manager(&manager);
+    let db = options.open().unwrap();
+    db.put(0, b"key", b"value").unwrap();
+    drop(db);
+    assert!(!path.exists());
+
+    let db = options.open().unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert_eq!(
+        db.get(0, b"key", slice.as_mut()).unwrap().unwrap(),
+        b"value"
+    );
+    // Recovery writes the WAL to a table file, which the manager can only
+    // find in the env.
+    assert!(manager.total_size() > 0);
+}
+
+#[test]
+fn test_fault_injection_fs() {
+    let fs = FaultInjectionFs::new(&Env::new_mem());
+    let mut options = DbOptions::new(Path::new("/autorocks-fault-injection-test"), 1);
+    options
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .env(&fs.env());
+    let db = options.open().unwrap();
+    let mut opts = WriteOpts::new();
+    opts.sync(true);
+    db.put_with_options(opts.as_inner(), 0, b"a", b"1").unwrap();
+    db.put(0, b"b", b"2").unwrap();
+    drop(db);
+
+    // Simulate a crash.
+    fs.drop_unsynced_data().unwrap();
+    let db = options.open().unwrap();
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert_eq!(db.get(0, b"a", slice.as_mut()).unwrap().unwrap(), b"1");
+    assert!(db.get(0, b"b", slice.as_mut()).unwrap().is_none());
+
+    fs.set_fail_writes(true);
+    assert!(db.put(0, b"c", b"3").is_err());
+    assert!(db.background_error().is_some());
+    assert!(db.put(0, b"c", b"3").is_err());
+
+    // Recover once the file system works again.
+    fs.set_fail_writes(false);
+    db.resume().unwrap();
+    assert!(db.background_error().is_none());
+    db.put(0, b"d", b"4").unwrap();
+    assert_eq!(db.get(0, b"d", slice.as_mut()).unwrap().unwrap(), b"4");
+}
+
+#[test]
+fn test_blob_files() {
+    let dir = tempdir().unwrap();
+    let mut cf_options = CfOptions::new();
+    cf_options
+        .enable_blob_files(true)
+        .min_blob_size(64)
+        .blob_file_size(1 << 20)
+        .enable_blob_garbage_collection(true)
+        .blob_garbage_collection_age_cutoff(0.5)
+        .blob_garbage_collection_force_threshold(0.8)
+        .blob_cache(&Cache::new_lru(1 << 20, -1, false, 0.5));
+    let mut options = DbOptions::new(dir.path(), 1);
+    options
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .cf_options(0, &cf_options);
+    let db = options.open().unwrap();
+    let large = vec![7; 1 << 10];
+    db.put(0, b"large", &large).unwrap();
+    db.put(0, b"small", b"value").unwrap();
+    drop(db);
+    // Recovery writes the WAL to table and blob files.
+    let db = options.open().unwrap();
+    assert_eq!(db.get_int_property(0, properties::NUM_BLOB_FILES), Some(1));
+    assert!(
+        db.get_int_property(0, properties::LIVE_BLOB_FILE_SIZE)
+            .unwrap()
+            > large.len() as u64
+    );
+    assert_eq!(
+        db.get_int_property(0, properties::BLOB_CACHE_CAPACITY),
+        Some(1 << 20)
+    );
+
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert_eq!(db.get(0, b"large", slice.as_mut()).unwrap().unwrap(), large);
+    assert_eq!(
+        db.get(0, b"small", slice.as_mut()).unwrap().unwrap(),
+        b"value"
+    );
+    let entries: Vec<_> = db.iter(0, Direction::Forward).collect();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(&*entries[0].1, &large[..]);
+    let txn = db.begin_transaction();
+    assert_eq!(
+        txn.get(0, b"large", slice.as_mut()).unwrap().unwrap(),
+        large
+    );
+}
+
+#[derive(Default)]
+struct EntityHandler {
+    entities: Vec<(usize, Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>,
+}
+
+impl WriteBatchHandler for EntityHandler {
+    fn put(&mut self, _col: usize, _key: &[u8], _value: &[u8]) {}
+
+    fn delete(&mut self, _col: usize, _key: &[u8]) {}
+
+    fn delete_range(&mut self, _col: usize, _begin_key: &[u8], _end_key: &[u8]) {}
+
+    fn put_entity(&mut self, col: usize, key: &[u8], columns: &[(&[u8], &[u8])]) {
+        let columns = columns
+            .iter()
+            .map(|(n, v)| (n.to_vec(), v.to_vec()))
+            .collect();
+        self.entities.push((col, key.to_vec(), columns));
+    }
+}
+
+#[test]
+fn test_wide_columns() {
+    let (db, _dir) = open_temp(1);
+    db.put_entity(0, b"a", &[("name", "alice"), ("age", "30")])
+        .unwrap();
+    db.put(0, b"b", b"plain").unwrap();
+    let mut batch = db.new_write_batch();
+    batch.put_entity(0, b"c", &[("x", "1")]).unwrap();
+    let mut handler = EntityHandler::default();
+    batch.iterate(&mut handler).unwrap();
+    assert_eq!(
+        handler.entities,
+        [(0, b"c".to_vec(), vec![(b"x".to_vec(), b"1".to_vec())])]
+    );
+    db.write(&mut batch).unwrap();
+
+    assert_eq!(
+        db.get_entity(0, b"a").unwrap().unwrap(),
+        vec![
+            (b"age".to_vec(), b"30".to_vec()),
+            (b"name".to_vec(), b"alice".to_vec())
+        ]
+    );
+    assert_eq!(
+        db.get_entity(0, b"b").unwrap().unwrap(),
+        vec![(vec![], b"plain".to_vec())]
+    );
+    assert!(db.get_entity(0, b"z").unwrap().is_none());
+
+    // The first update is the entity written with put_entity.
+    let (_, update) = db.get_updates_since(1).unwrap().next().unwrap().unwrap();
+    let mut handler = EntityHandler::default();
+    update.iterate(&mut handler).unwrap();
+    assert_eq!(
+        handler.entities,
+        [(
+            0,
+            b"a".to_vec(),
+            vec![
+                (b"age".to_vec(), b"30".to_vec()),
+                (b"name".to_vec(), b"alice".to_vec())
+            ]
+        )]
+    );
+
+    let mut iter = db.iter(0, Direction::Forward);
+    iter.next().unwrap();
+    assert_eq!(
+        iter.columns().unwrap(),
+        vec![
+            (b"age".as_slice(), b"30".as_slice()),
+            (b"name".as_slice(), b"alice".as_slice())
+        ]
+    );
+    iter.next().unwrap();
+    assert_eq!(
+        iter.columns().unwrap(),
+        vec![(b"".as_slice(), b"plain".as_slice())]
+    );
+    iter.next().unwrap();
+    assert_eq!(
+        iter.columns().unwrap(),
+        vec![(b"x".as_slice(), b"1".as_slice())]
+    );
+    assert!(iter.next().is_none());
+    assert!(iter.columns().is_none());
+
+    // Entity writes wait for key locks held by transactions.
+    let mut tx = db.begin_transaction();
+    tx.put(0, b"a", b"locked").unwrap();
+    let err = db.put_entity(0, b"a", &[("x", "2")]).unwrap_err();
+    assert!(err.is_timed_out());
+    tx.rollback().unwrap();
+    db.put_entity(0, b"a", &[("x", "2")]).unwrap();
+}
+
+#[test]
+fn test_user_defined_timestamps() {
+    let dir = tempdir().unwrap();
+    let mut cf_options = CfOptions::new();
+    cf_options.u64_timestamp_comparator();
+    let mut options = DbOptions::new(dir.path(), 1);
+    options
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .cf_options(0, &cf_options);
+    let db = options.open().unwrap();
+    db.put_with_ts(0, b"key", 10, b"v1").unwrap();
+    db.put_with_ts(0, b"key", 20, b"v2").unwrap();
+
+    moveit! {
+        let mut slice = PinnableSlice::new();
+    }
+    assert!(db.get(0, b"key", slice.as_mut()).is_err());
+    assert!(db.get_at(0, b"key", 5, slice.as_mut()).unwrap().is_none());
+    assert_eq!(
+        db.get_at(0, b"key", 15, slice.as_mut()).unwrap().unwrap(),
+        b"v1"
+    );
+    assert_eq!(
+        db.get_at(0, b"key", 25, slice.as_mut()).unwrap().unwrap(),
+        b"v2"
+    );
+
+    let mut read_opts = ReadOpts::new();
+    read_opts.timestamp(15);
+    let values: Vec<_> = db
+        .iter_with_options(read_opts.as_inner(), 0, Direction::Forward)
+        .map(|(_, v)| v)
+        .collect();
+    assert_eq!(values, vec![b"v1".to_vec().into_boxed_slice()]);
+
+    read_opts.timestamp(25).iter_start_ts(0);
+    let mut iter = db.iter_with_options(read_opts.as_inner(), 0, Direction::Forward);
+    // The iterator keeps its own copy of the timestamps.
+    drop(read_opts);
+    let mut versions = vec![];
+    while let Some((_, v)) = iter.next() {
+        versions.push((iter.timestamp().unwrap(), v.to_vec()));
+    }
+    assert_eq!(versions, vec![(20, b"v2".to_vec()), (10, b"v1".to_vec())]);
+
+    // Transactions with a snapshot also need a read timestamp.
+    let mut tx = db.begin_transaction();
+    tx.set_read_timestamp_for_validation(25).unwrap();
+    tx.put(0, b"key", b"v3").unwrap();
+    tx.set_commit_timestamp(30).unwrap();
+    tx.commit().unwrap();
+    assert_eq!(
+        db.get_at(0, b"key", 30, slice.as_mut()).unwrap().unwrap(),
+        b"v3"
+    );
+
+    db.delete_with_ts(0, b"key", 40).unwrap();
+    assert!(db.get_at(0, b"key", 40, slice.as_mut()).unwrap().is_none());
+    assert_eq!(
+        db.get_at(0, b"key", 35, slice.as_mut()).unwrap().unwrap(),
+        b"v3"
+    );
+
+    assert_eq!(db.full_history_ts_low(0).unwrap(), 0);
+    db.increase_full_history_ts_low(0, 15).unwrap();
+    assert_eq!(db.full_history_ts_low(0).unwrap(), 15);
+    assert!(db.increase_full_history_ts_low(0, 5).is_err());
+}